@@ -0,0 +1,110 @@
+use crate::prelude::*;
+use crate::canvas::Canvas;
+
+// Trauma lost per second; shake is trauma squared, so it dies off quickly at the end
+const TRAUMA_DECAY: f32 = 1.2;
+// Strongest shake: offset as percent of screen height, and rotation in degrees
+const MAX_SHAKE_OFFSET: f32 = 0.025;
+const MAX_SHAKE_ANGLE: f32 = 1.5;
+const SHAKE_FREQUENCY: f32 = 25.0;
+// Above this height (percent of screen height) the camera starts following the dino up
+const FOLLOW_START: f32 = 0.25;
+// How far the camera can rise, as percent of screen height
+const MAX_FOLLOW: f32 = 0.08;
+const FOLLOW_SPEED: f32 = 4.0;
+// Death zoom: how close it gets, and how long it takes to get there
+const DEATH_ZOOM: f32 = 1.6;
+const DEATH_ZOOM_TIME: f32 = 0.8;
+
+/// Presentation-only view of the world: shake, follow and zoom are applied when drawing,
+/// so gameplay keeps working in plain screen coordinates
+pub struct GameCamera {
+    // 0..1, added by hits and drained over time
+    trauma: f32,
+    time: f32,
+    // Current upward shift, as percent of screen height
+    follow: f32,
+    // 0..1 progress of the zoom onto a dead dino
+    death_zoom: f32,
+    // Where to zoom to, in pixels
+    focus: Vec2,
+}
+
+impl GameCamera {
+    pub fn new() -> Self {
+        Self {
+            trauma: 0.0,
+            time: 0.0,
+            follow: 0.0,
+            death_zoom: 0.0,
+            focus: Vec2::ZERO,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Shake the screen; trauma stacks up to a maximum of 1
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    /// Follow the dino (center in pixels) up when it flies high, and zoom in on it once dead
+    pub fn update(&mut self, focus: Vec2, dead: bool, dt: f32) {
+        self.time += dt;
+        self.trauma = (self.trauma - TRAUMA_DECAY * dt).max(0.0);
+        self.focus = focus;
+
+        let height = focus.y / screen_height();
+        let target_follow = ((FOLLOW_START - height) / FOLLOW_START).clamp(0.0, 1.0) * MAX_FOLLOW;
+        self.follow += (target_follow - self.follow) * (FOLLOW_SPEED * dt).min(1.0);
+
+        let zoom_step = dt / DEATH_ZOOM_TIME;
+        self.death_zoom = if dead {
+            (self.death_zoom + zoom_step).min(1.0)
+        } else {
+            (self.death_zoom - zoom_step * 2.0).max(0.0)
+        };
+    }
+
+    /// Camera to draw the world onto the canvas with
+    pub fn world_camera(&self, canvas: &Canvas) -> Camera2D {
+        let size = Vec2::new(screen_width(), screen_height());
+        let shake = self.trauma * self.trauma;
+        // Smooth pseudo-random wobble from a few mismatched sine waves
+        let t = self.time * SHAKE_FREQUENCY;
+        let wobble = Vec2::new((t * 1.1).sin() + (t * 2.3).sin() * 0.5, (t * 1.7).cos() + (t * 2.9).sin() * 0.5) / 1.5;
+        let offset = wobble * shake * MAX_SHAKE_OFFSET * size.y;
+
+        // Ease in on the dino, and zoom in a touch while shaking so the edges never show
+        let ease = self.death_zoom * self.death_zoom * (3.0 - 2.0 * self.death_zoom);
+        let zoom = (1.0 + (DEATH_ZOOM - 1.0) * ease) * (1.0 + 2.0 * MAX_SHAKE_OFFSET * shake);
+        let center = size / 2.0 - Vec2::new(0.0, self.follow * size.y);
+        // Keep the zoomed view inside the screen, so nothing beyond the world's edges shows
+        let half_view = size / 2.0 / zoom;
+        let view_center = center + (self.focus - center) * ease;
+        let target = Vec2::new(
+            view_center.x.clamp(half_view.x, size.x - half_view.x),
+            view_center.y.min(size.y - half_view.y),
+        ) + offset;
+
+        // Render targets are drawn y-up, so y is flipped like `Camera2D::from_display_rect` does
+        Camera2D {
+            target,
+            zoom: Vec2::new(2.0 / size.x, -2.0 / size.y) * zoom,
+            rotation: (t * 0.9).sin() * shake * MAX_SHAKE_ANGLE,
+            render_target: Some(canvas.target()),
+            ..Default::default()
+        }
+    }
+
+    /// Where a point in the world ends up on the canvas, in canvas pixels
+    pub fn world_to_canvas(camera: &Camera2D, point: Vec2) -> Vec2 {
+        let projected = camera.matrix().transform_point3(point.extend(0.0));
+        Vec2::new(
+            (projected.x / 2.0 + 0.5) * screen_width(),
+            (0.5 - projected.y / 2.0) * screen_height(),
+        )
+    }
+}
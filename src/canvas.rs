@@ -0,0 +1,62 @@
+use macroquad::prelude::*;
+
+// Fixed size the game is laid out and drawn at, in virtual pixels
+pub const WIDTH: f32 = 1280.0;
+pub const HEIGHT: f32 = 720.0;
+const LETTERBOX_COLOR: Color = BLACK;
+
+/// Width of the virtual canvas; re-exported by `crate::prelude` in place of macroquad's,
+/// so layout and gameplay don't depend on the shape of the window.
+pub fn screen_width() -> f32 {
+    WIDTH
+}
+
+pub fn screen_height() -> f32 {
+    HEIGHT
+}
+
+/// Off-screen canvas the game draws into, then scaled to fit the window with black bars
+/// on the sides or top and bottom
+pub struct Canvas {
+    target: RenderTarget,
+}
+
+impl Canvas {
+    pub fn new() -> Self {
+        let target = render_target(WIDTH as u32, HEIGHT as u32);
+        target.texture.set_filter(FilterMode::Nearest);
+        Self { target }
+    }
+
+    /// Camera drawing straight onto the canvas, in canvas pixels
+    pub fn camera(&self) -> Camera2D {
+        let mut camera = Camera2D::from_display_rect(Rect::new(0.0, 0.0, WIDTH, HEIGHT));
+        camera.render_target = Some(self.target.clone());
+        camera
+    }
+
+    /// The canvas texture, for cameras of their own (e.g. the world camera) to draw into
+    pub fn target(&self) -> RenderTarget {
+        self.target.clone()
+    }
+
+    /// Scale the finished canvas up to the largest size that fits the window, centred
+    pub fn present(&self) {
+        set_default_camera();
+        clear_background(LETTERBOX_COLOR);
+        let (window_width, window_height) = (macroquad::window::screen_width(), macroquad::window::screen_height());
+        let scale = (window_width / WIDTH).min(window_height / HEIGHT);
+        let size = Vec2::new(WIDTH, HEIGHT) * scale;
+        draw_texture_ex(
+            &self.target.texture,
+            (window_width - size.x) / 2.0,
+            (window_height - size.y) / 2.0,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(size),
+                flip_y: true,
+                ..Default::default()
+            },
+        );
+    }
+}
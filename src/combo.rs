@@ -0,0 +1,136 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Highest combo multiplier
+const MAX_MULTIPLIER: u32 = 8;
+// Seconds without a new event before the multiplier drops a step
+const DECAY_TIME: f32 = 2.5;
+const POPUP_LIFETIME: f32 = 1.0;
+// How far popups rise over their lifetime, as percent of screen height
+const POPUP_RISE: f32 = 0.08;
+
+/// Things the player did that are worth bonus score
+#[derive(Clone, Copy, PartialEq)]
+pub enum ScoreEvent {
+    // Jumped cleanly over an obstacle
+    Cleared,
+    AteFly,
+    Stomp,
+    // Flew past an obstacle
+    FlewThrough,
+    // Got past an obstacle with only a sliver of room to spare
+    NearMiss,
+    BossHit,
+}
+
+impl ScoreEvent {
+    fn points(self) -> u32 {
+        match self {
+            ScoreEvent::Cleared => 20,
+            ScoreEvent::AteFly => 30,
+            ScoreEvent::Stomp => 50,
+            ScoreEvent::FlewThrough => 20,
+            ScoreEvent::NearMiss => 40,
+            ScoreEvent::BossHit => 100,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ScoreEvent::Cleared => "Clear",
+            ScoreEvent::AteFly => "Yum",
+            ScoreEvent::Stomp => "Stomp",
+            ScoreEvent::FlewThrough => "Fly-by",
+            ScoreEvent::NearMiss => "Close call",
+            ScoreEvent::BossHit => "Boss hit",
+        }
+    }
+}
+
+/// Floating "+points" text where an event happened
+#[derive(Clone, Serialize, Deserialize)]
+struct ScorePopup {
+    text: String,
+    // Screen percentages
+    x_percent: f32,
+    y_percent: f32,
+    age: f32,
+}
+
+/// Combo multiplier built up by score events; decays over time and breaks on damage
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Combo {
+    multiplier: u32,
+    decay_timer: f32,
+    popups: Vec<ScorePopup>,
+}
+
+impl Combo {
+    pub fn new() -> Self {
+        Self {
+            multiplier: 1,
+            decay_timer: 0.0,
+            popups: Vec::new(),
+        }
+    }
+
+    /// Score an event at a screen position (pixels) and grow the combo.
+    /// `bonus` is any extra multiplier on top (e.g. the score power-up). Returns the points earned.
+    pub fn register(&mut self, event: ScoreEvent, position: Vec2, bonus: u32) -> u32 {
+        let points = event.points() * self.multiplier * bonus;
+        self.popups.push(ScorePopup {
+            text: format!("+{} {}", points, event.label()),
+            x_percent: position.x / screen_width(),
+            y_percent: position.y / screen_height(),
+            age: 0.0,
+        });
+        self.multiplier = (self.multiplier + 1).min(MAX_MULTIPLIER);
+        self.decay_timer = DECAY_TIME;
+        points
+    }
+
+    /// Drop back to no combo, e.g. after taking damage
+    pub fn break_combo(&mut self) {
+        self.multiplier = 1;
+        self.decay_timer = 0.0;
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        if self.multiplier > 1 {
+            self.decay_timer -= dt;
+            if self.decay_timer <= 0.0 {
+                self.multiplier -= 1;
+                self.decay_timer = DECAY_TIME;
+            }
+        }
+
+        for popup in &mut self.popups {
+            popup.age += dt;
+        }
+        self.popups.retain(|p| p.age < POPUP_LIFETIME);
+    }
+
+    pub fn draw_popups(&self) {
+        let font_size = screen_height() * 0.035;
+        for popup in &self.popups {
+            let progress = popup.age / POPUP_LIFETIME;
+            let x = screen_width() * popup.x_percent;
+            let y = screen_height() * (popup.y_percent - POPUP_RISE * progress);
+            let color = Color { a: 1.0 - progress, ..ORANGE };
+            let text_width = measure_text(&popup.text, None, font_size as u16, 1.0).width;
+            draw_text(&popup.text, x - text_width / 2.0, y, font_size, color);
+        }
+    }
+
+    /// "x3" next to the score, with a bar showing time left before it drops
+    pub fn draw_multiplier(&self, x: f32, y: f32, font_size: f32) {
+        if self.multiplier <= 1 {
+            return;
+        }
+        let text = format!("x{}", self.multiplier);
+        draw_text(&text, x, y, font_size, ORANGE);
+        let width = measure_text(&text, None, font_size as u16, 1.0).width;
+        let fraction = (self.decay_timer / DECAY_TIME).clamp(0.0, 1.0);
+        draw_rectangle(x, y + font_size * 0.15, width * fraction, font_size * 0.1, ORANGE);
+    }
+}
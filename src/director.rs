@@ -0,0 +1,62 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Weather, WeatherKind};
+use crate::rng::Rng;
+
+// Score at which difficulty tops out
+const MAX_DIFFICULTY_SCORE: f32 = 15000.0;
+// Calm spell before the first weather event, in seconds
+const FIRST_WEATHER_DELAY: f32 = 25.0;
+
+/// Paces the run: difficulty grows with the score, and weather is scheduled to match
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Director {
+    // Seconds until the next weather event may start
+    weather_timer: f32,
+    rng: Rng,
+}
+
+impl Director {
+    pub fn new(rng_seed: u64) -> Self {
+        Self {
+            weather_timer: FIRST_WEATHER_DELAY,
+            rng: Rng::new(rng_seed),
+        }
+    }
+
+    /// 0 at the start of a run, 1 once the score is high enough
+    pub fn difficulty(score: u32) -> f32 {
+        (score as f32 / MAX_DIFFICULTY_SCORE).min(1.0)
+    }
+
+    /// Start new weather once the last event has cleared and the calm spell is over.
+    /// Harder runs get heavier, longer and more frequent weather, and fog only shows up later on.
+    pub fn update(&mut self, weather: &mut Weather, score: u32, dt: f32) {
+        if weather.is_active() {
+            return;
+        }
+        self.weather_timer -= dt;
+        if self.weather_timer > 0.0 {
+            return;
+        }
+
+        let difficulty = Self::difficulty(score);
+        let kinds: &[WeatherKind] = if difficulty > 0.3 {
+            &[WeatherKind::Rain, WeatherKind::Wind, WeatherKind::Fog]
+        } else {
+            &[WeatherKind::Rain, WeatherKind::Wind]
+        };
+        let (kind, duration, wind_direction, delay) = self.rng.draw(|rng| {
+            (
+                kinds[rng.gen_range(0, kinds.len())],
+                rng.gen_range(10.0, 18.0) * (1.0 + difficulty * 0.5),
+                if rng.gen_range(0, 2) == 0 { -1.0 } else { 1.0 },
+                rng.gen_range(20.0, 35.0) * (1.0 - difficulty * 0.5),
+            )
+        });
+        let intensity = 0.5 + 0.5 * difficulty;
+        weather.start(kind, duration, intensity, wind_direction);
+        self.weather_timer = delay;
+    }
+}
@@ -1,13 +1,46 @@
-use macroquad::prelude::*;
+use crate::prelude::*;
 
+mod camera;
+mod canvas;
+mod combo;
+mod director;
 mod models;
-
-use models::{Cloud, Dino, Fly, Obstacle, Rock, Cactus, ground_y, dino::DeathCause};
+mod prelude;
+mod profile;
+mod rewind;
+mod rng;
+mod run_stats;
+mod save;
+mod shop;
+
+use models::{contact, Biome, Biomes, Boss, Burst, Cloud, Coin, Contact, DayCycle, Dino, ObstacleRegistry, Parallax, Particles, Pickup, Projectile, Terrain, Tracked, Weather, dino::{DeathCause, Effect, Status}};
+use camera::GameCamera;
+use canvas::Canvas;
+use combo::{Combo, ScoreEvent};
+use director::Director;
+use rewind::{RewindBuffer, Snapshot};
+use run_stats::RunStats;
+use profile::{Profile, SCORING_VERSION};
+use save::SaveGame;
+use shop::Shop;
 
 // Game constants (as percentages where applicable)
 const GAME_SPEED_PERCENT: f32 = 0.2;       // 40% of screen width per second
 const GAME_SPEED_INCREMENT: f32 = 0.015;   // Speed increase per second
 const OBSTACLE_SPAWN_TIME: f32 = 1.5;
+const REWIND_CHARGES: u32 = 3;             // Rewinds available per run
+const PICKUP_SPAWN_CHANCE: f32 = 0.15;     // Chance a power-up comes along with an obstacle
+const MAGNET_STRENGTH: f32 = 4.0;
+const COIN_ROW_CHANCE: f32 = 0.3;          // Chance a row of coins comes along with an obstacle
+const SCORE_PER_COIN: u32 = 100;           // Score converted into coins at the end of a run
+const SCORE_PER_SCREEN: f32 = 300.0;       // Points for each screen width travelled
+const NEAR_MISS_MARGIN: f32 = 0.04;        // Gap counted as a near miss, as percent of screen height
+const NEAR_MISS_SLOW_MOTION: f32 = 0.5;    // Seconds the world slows down after a near miss
+const NEAR_MISS_TIME_SCALE: f32 = 0.4;
+const BOSS_SCORE_INTERVAL: u32 = 3000;     // A boss shows up every this many points
+const BOSS_REWARD: u32 = 1000;             // Score for defeating a boss
+const HIT_TRAUMA: f32 = 0.4;               // Screen shake from taking damage
+const DEATH_TRAUMA: f32 = 0.8;             // and from being killed outright
 
 // Game state enum
 #[derive(PartialEq)]
@@ -15,31 +48,68 @@ enum GameState {
     Ready,
     Playing,
     GameOver,
+    Shop,
 }
 
 // Main game structure
 struct Game {
     dino: Dino,
-    obstacles: Vec<Box<dyn Obstacle>>,
+    obstacles: Vec<Tracked>,
+    projectiles: Vec<Projectile>,
+    pickups: Vec<Pickup>,
+    coins: Vec<Coin>,
     clouds: Vec<Cloud>,
+    particles: Particles,
+    // Shake, follow and zoom; only affects how the world is drawn
+    camera: GameCamera,
+    // Fixed-size canvas everything is drawn on, letterboxed into the window
+    canvas: Canvas,
+    // Scenery layers behind and in front of the world, one set per biome
+    parallax: Vec<Parallax>,
+    biomes: Biomes,
+    day_cycle: DayCycle,
+    weather: Weather,
+    // Schedules weather to match how far the run has got
+    director: Director,
+    terrain: Terrain,
+    // Boss fight in progress; regular spawning pauses until it's gone
+    boss: Option<Boss>,
+    next_boss_score: u32,
     score: u32,
+    // Distance points not yet added to the score (less than one)
+    score_progress: f32,
+    combo: Combo,
+    stats: RunStats,
+    // Slow motion left after a near miss, in real seconds
+    slow_motion_timer: f32,
     high_score: u32,
     game_speed_percent: f32,
-    ground_displacement: f32,
     spawn_timer: f32,
     state: GameState,
-    // Textures loaded from each model
-    rock_texture: Texture2D,
-    cactus_textures: (Texture2D, Texture2D),
-    fly_textures: (Texture2D, Texture2D),
+    // Seed for the global RNG, carried between spawns so runs can be rewound
+    rng_seed: u64,
+    rewind: RewindBuffer,
+    rewind_charges: u32,
+    is_rewinding: bool,
+    // Run saved on the last quit, offered as "Continue" on the title screen
+    saved_run: Option<SaveGame>,
+    // Currency and upgrades kept between sessions
+    profile: Profile,
+    shop: Shop,
+    // Coins picked up this run, and how many have been banked into the profile
+    run_coins: u32,
+    coins_awarded: u32,
+    // When this run started; identifies its leaderboard entry
+    run_started_at: f64,
+    // Obstacle types, their textures and factories
+    obstacle_registry: ObstacleRegistry,
+    cloud_texture: Texture2D,
 }
 
 impl Game {
     async fn new() -> Self {
-        // Load textures from each model
-        let rock_texture = Rock::load_texture().await;
-        let cactus_textures = Cactus::load_textures().await;
-        let fly_textures = Fly::load_textures().await;
+        let mut obstacle_registry = ObstacleRegistry::with_builtin().await;
+        obstacle_registry.load_textures(&[Boss::TEXTURE]).await;
         let cloud_texture = Cloud::load_texture().await;
 
         let clouds = (0..5)
@@ -50,30 +120,199 @@ impl Game {
             })
             .collect();
 
+        let rng_seed = (miniquad::date::now() * 1000.0) as u64;
+        let profile = Profile::load();
+        let mut dino = Dino::new().await;
+        dino.set_tuning(profile.dino_tuning());
+
         Self {
-            dino: Dino::new().await,
+            dino,
             obstacles: Vec::new(),
+            projectiles: Vec::new(),
+            pickups: Vec::new(),
+            coins: Vec::new(),
             clouds,
+            particles: Particles::new(),
+            camera: GameCamera::new(),
+            canvas: Canvas::new(),
+            parallax: Biome::ALL.iter().map(|b| Parallax::new(b.layers())).collect(),
+            biomes: Biomes::new(),
+            day_cycle: DayCycle::new(),
+            weather: Weather::new(),
+            director: Director::new(rng_seed.wrapping_add(2)),
+            terrain: Terrain::new(rng_seed),
+            boss: None,
+            next_boss_score: BOSS_SCORE_INTERVAL,
             score: 0,
-            high_score: 0,
+            score_progress: 0.0,
+            combo: Combo::new(),
+            stats: RunStats::default(),
+            slow_motion_timer: 0.0,
+            high_score: profile.best_score(),
             game_speed_percent: GAME_SPEED_PERCENT,
-            ground_displacement: 0.0,
             spawn_timer: OBSTACLE_SPAWN_TIME,
             state: GameState::Ready,
-            rock_texture,
-            cactus_textures,
-            fly_textures,
+            rng_seed,
+            rewind: RewindBuffer::new(),
+            rewind_charges: REWIND_CHARGES,
+            is_rewinding: false,
+            saved_run: SaveGame::load(),
+            profile,
+            shop: Shop::new(),
+            run_coins: 0,
+            coins_awarded: 0,
+            run_started_at: miniquad::date::now(),
+            obstacle_registry,
+            cloud_texture,
         }
     }
 
     fn reset(&mut self) {
         self.dino.reset();
         self.obstacles.clear();
+        self.projectiles.clear();
+        self.pickups.clear();
+        self.coins.clear();
+        self.particles.clear();
+        self.camera.reset();
+        self.run_coins = 0;
+        self.coins_awarded = 0;
+        self.run_started_at = miniquad::date::now();
+        self.terrain = Terrain::new(self.rng_seed.wrapping_add(1));
+        self.boss = None;
+        self.next_boss_score = BOSS_SCORE_INTERVAL;
+        self.day_cycle = DayCycle::new();
+        self.biomes = Biomes::new();
+        self.weather = Weather::new();
+        self.director = Director::new(self.rng_seed.wrapping_add(2));
         self.score = 0;
+        self.score_progress = 0.0;
+        self.combo = Combo::new();
+        self.stats = RunStats::default();
+        self.slow_motion_timer = 0.0;
         self.game_speed_percent = GAME_SPEED_PERCENT;
-        self.ground_displacement = 0.0;
         self.spawn_timer = OBSTACLE_SPAWN_TIME;
         self.state = GameState::Playing;
+        self.rewind.clear();
+        self.rewind_charges = REWIND_CHARGES;
+        self.is_rewinding = false;
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            dino: self.dino.snapshot(),
+            obstacles: self.obstacles.iter().map(|o| o.snapshot()).collect(),
+            projectiles: self.projectiles.clone(),
+            pickups: self.pickups.clone(),
+            coins: self.coins.clone(),
+            run_coins: self.run_coins,
+            terrain: self.terrain.clone(),
+            day_cycle: self.day_cycle.clone(),
+            biomes: self.biomes.clone(),
+            weather: self.weather.clone(),
+            director: self.director.clone(),
+            boss: self.boss.as_ref().map(|b| b.snapshot()),
+            next_boss_score: self.next_boss_score,
+            score: self.score,
+            score_progress: self.score_progress,
+            combo: self.combo.clone(),
+            stats: self.stats.clone(),
+            game_speed_percent: self.game_speed_percent,
+            spawn_timer: self.spawn_timer,
+            rng_seed: self.rng_seed,
+        }
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        self.dino.restore(&snapshot.dino);
+        self.obstacles = snapshot
+            .obstacles
+            .iter()
+            .filter_map(|saved| self.obstacle_registry.restore(saved))
+            .collect();
+        self.projectiles = snapshot.projectiles.clone();
+        self.pickups = snapshot.pickups.clone();
+        self.coins = snapshot.coins.clone();
+        self.run_coins = snapshot.run_coins;
+        self.terrain = snapshot.terrain.clone();
+        self.day_cycle = snapshot.day_cycle.clone();
+        self.biomes = snapshot.biomes.clone();
+        self.weather = snapshot.weather.clone();
+        self.director = snapshot.director.clone();
+        self.boss = snapshot
+            .boss
+            .clone()
+            .map(|state| Boss::restore(state, self.obstacle_registry.assets().texture(Boss::TEXTURE)));
+        self.next_boss_score = snapshot.next_boss_score;
+        self.score = snapshot.score;
+        self.score_progress = snapshot.score_progress;
+        self.combo = snapshot.combo.clone();
+        self.stats = snapshot.stats.clone();
+        self.game_speed_percent = snapshot.game_speed_percent;
+        self.spawn_timer = snapshot.spawn_timer;
+        self.rng_seed = snapshot.rng_seed;
+    }
+
+    // Write the current run to disk if there is one worth continuing
+    fn save_on_quit(&self) {
+        if self.state != GameState::Playing || self.dino.is_dead() {
+            return;
+        }
+        SaveGame {
+            world: self.snapshot(),
+            clouds: self.clouds.iter().map(|c| c.snapshot()).collect(),
+            high_score: self.high_score,
+            rewind_charges: self.rewind_charges,
+        }
+        .write();
+    }
+
+    fn continue_saved_run(&mut self) {
+        let Some(save) = self.saved_run.take() else {
+            return;
+        };
+        SaveGame::delete();
+
+        self.reset();
+        self.restore(&save.world);
+        self.clouds = save
+            .clouds
+            .iter()
+            .map(|state| Cloud::restore(*state, self.cloud_texture.clone()))
+            .collect();
+        self.high_score = self.high_score.max(save.high_score);
+        self.rewind_charges = save.rewind_charges;
+    }
+
+    // Bank this run's coins (pickups plus a share of the score) into the profile.
+    // Only the difference is added, so a rewound-and-resumed run isn't paid twice.
+    fn award_run_coins(&mut self) {
+        let earned = self.run_coins + self.score / SCORE_PER_COIN;
+        self.profile.coins += earned.saturating_sub(self.coins_awarded);
+        self.coins_awarded = self.coins_awarded.max(earned);
+        self.profile.write();
+    }
+
+    fn can_rewind(&self) -> bool {
+        self.is_rewinding || (self.rewind_charges > 0 && !self.rewind.is_empty())
+    }
+
+    // Step the world backwards while the rewind key is held
+    fn rewind_step(&mut self, dt: f32) {
+        if !self.is_rewinding {
+            self.is_rewinding = true;
+            self.rewind_charges -= 1;
+        }
+        if let Some(snapshot) = self.rewind.rewind(dt) {
+            self.restore(&snapshot);
+        }
+    }
+
+    fn update_clouds(&mut self, dt: f32) {
+        let sprites = self.biomes.cloud_sprites();
+        for cloud in &mut self.clouds {
+            cloud.update(dt, sprites);
+        }
     }
 
     // Convert percentage speed to pixel speed
@@ -81,34 +320,64 @@ impl Game {
         screen_width() * self.game_speed_percent
     }
 
-    fn spawn_obstacle(&mut self) {
-        let obstacle_type = rand::gen_range(0, 10);
+    // Tell the dino what ground is under its feet this frame
+    fn update_dino_ground(&mut self) {
+        let (left, right, feet_y) = self.dino.feet();
+        self.dino.set_ground(self.terrain.surface_under(left, right, feet_y));
+    }
+
+    // Place the next obstacle, unless the biome has none or the ground it would cover isn't flat yet
+    fn spawn_obstacle(&mut self) -> bool {
         let spawn_x = screen_width() + screen_width() * 0.05;
-        let obstacle: Box<dyn Obstacle> = match obstacle_type {
-            0..=3 => Box::new(Rock::new(spawn_x, self.rock_texture.clone())),
-            4..=6 => Box::new(Cactus::new(spawn_x, self.cactus_textures.0.clone(), self.cactus_textures.1.clone())),
-            _ => Box::new(Fly::new(spawn_x, self.fly_textures.0.clone(), self.fly_textures.1.clone())),
+        let Some(obstacle) = self.obstacle_registry.spawn_random(spawn_x, self.biomes.obstacles()) else {
+            return false;
         };
+        if let Some((left, right)) = obstacle.footprint() {
+            if !self.terrain.is_flat_between(left, right) {
+                return false;
+            }
+        }
         self.obstacles.push(obstacle);
+
+        // Sometimes a power-up trails behind the obstacle
+        if rand::gen_range(0.0, 1.0) < PICKUP_SPAWN_CHANCE {
+            let offset = screen_width() * rand::gen_range(0.15, 0.25);
+            self.pickups.push(Pickup::new(spawn_x + offset));
+        } else if rand::gen_range(0.0, 1.0) < COIN_ROW_CHANCE {
+            let offset = screen_width() * rand::gen_range(0.15, 0.25);
+            self.coins.extend(Coin::spawn_row(spawn_x + offset));
+        }
+        true
     }
 
     async fn update(&mut self) {
         let dt = get_frame_time();
+        self.camera.update(self.dino.get_hitbox().point(), self.dino.is_dead(), dt);
 
         match self.state {
             GameState::Ready => {
-                for cloud in &mut self.clouds {
-                    cloud.update(dt);
-                }
+                self.update_clouds(dt);
+                self.update_dino_ground();
                 self.dino.update(dt);
 
                 if is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::Up) {
-                    self.state = GameState::Playing;
-                    self.dino.is_standing = false;
+                    // Every run starts from a clean reset, so upgrades bought in the shop apply
+                    self.reset();
                     self.dino.jump();
+                } else if is_key_pressed(KeyCode::C) {
+                    self.continue_saved_run();
+                } else if is_key_pressed(KeyCode::S) {
+                    self.state = GameState::Shop;
                 }
             }
             GameState::Playing => {
+                if is_key_down(KeyCode::Backspace) && self.can_rewind() {
+                    self.rewind_step(dt);
+                    self.update_clouds(dt);
+                    return;
+                }
+                self.is_rewinding = false;
+
                 // Handle input
                 if is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::Up) {
                     self.dino.jump();
@@ -117,6 +386,12 @@ impl Game {
                 if is_key_pressed(KeyCode::E) {
                     self.dino.eat();
                 }
+                if is_key_pressed(KeyCode::LeftShift) || is_key_pressed(KeyCode::RightShift) {
+                    self.dino.dash();
+                }
+                if is_key_pressed(KeyCode::X) {
+                    self.dino.ground_slam();
+                }
                 
                 let mut fly_dir = 0.0;
                 if is_key_down(KeyCode::Up) {
@@ -128,7 +403,17 @@ impl Game {
                 self.dino.fly(is_key_down(KeyCode::F), fly_dir);
 
                 // Update dino
+                self.update_dino_ground();
+                self.dino.set_weather(self.weather.is_raining(), self.weather.wind());
                 self.dino.update(dt);
+                if self.dino.take_landing() {
+                    let (left, right, feet_y) = self.dino.feet();
+                    self.particles.emit(Burst::Dust, Vec2::new((left + right) / 2.0, feet_y));
+                }
+                if self.dino.is_flying {
+                    let hitbox = self.dino.get_hitbox();
+                    self.particles.stream(Burst::ManaTrail, Vec2::new(hitbox.x - hitbox.r, hitbox.y), 40.0, dt);
+                }
 
                 // Determine if world should update
                 let should_update_world = if !self.dino.is_dead() {
@@ -142,50 +427,225 @@ impl Game {
                 };
 
                 if should_update_world {
-                    // Update obstacles
-                    let speed = self.game_speed();
+                    // Slow-time power-up slows the world, not the dino
+                    // and so does a near miss, briefly
+                    let mut world_dt = dt * self.dino.time_scale();
+                    if self.slow_motion_timer > 0.0 {
+                        self.slow_motion_timer -= dt;
+                        world_dt *= NEAR_MISS_TIME_SCALE;
+                    }
+
+                    // Update obstacles and pickups
+                    let speed = self.game_speed() * self.dino.speed_multiplier();
+                    let dino_center = self.dino.get_hitbox().point();
+                    // Right edges before moving, to spot obstacles the dino gets past this frame
+                    let dino_back = dino_center.x - self.dino.get_hitbox().r;
+                    let previous_edges: Vec<f32> = self.obstacles.iter().map(|o| o.get_hitbox().right()).collect();
                     for obstacle in &mut self.obstacles {
-                        obstacle.update(speed, dt);
+                        obstacle.track_target(dino_center);
+                        let ground = self.terrain.ground_height(obstacle.get_hitbox().center().x);
+                        obstacle.set_ground(ground);
+                        obstacle.update(speed, world_dt);
+                        self.projectiles.extend(obstacle.take_projectiles());
+                    }
+                    if let Some(boss) = &mut self.boss {
+                        boss.track_target(dino_center);
+                        boss.update(speed, world_dt);
+                        self.projectiles.extend(boss.take_projectiles());
+                    }
+                    for projectile in &mut self.projectiles {
+                        projectile.update(speed, world_dt);
+                    }
+
+                    // Getting past an obstacle through the air, or by a hair, is worth a bonus.
+                    // Anything the dino ran into on the way doesn't count.
+                    let mut events = Vec::new();
+                    let dino_hitbox = self.dino.get_hitbox();
+                    let (_, _, feet_y) = self.dino.feet();
+                    for (obstacle, previous) in self.obstacles.iter_mut().zip(previous_edges) {
+                        obstacle.track(&dino_hitbox, feet_y);
+                        let hitbox = obstacle.get_hitbox();
+                        if previous < dino_back || hitbox.right() >= dino_back || obstacle.touched() {
+                            continue;
+                        }
+                        if obstacle.went_over() {
+                            let event = if self.dino.is_flying { ScoreEvent::FlewThrough } else { ScoreEvent::Cleared };
+                            events.push((event, hitbox.center()));
+                        }
+                        let near_miss = obstacle.closest_miss().is_some_and(|gap| gap < screen_height() * NEAR_MISS_MARGIN);
+                        if near_miss && !self.dino.is_dashing() {
+                            events.push((ScoreEvent::NearMiss, dino_hitbox.point()));
+                            self.slow_motion_timer = NEAR_MISS_SLOW_MOTION;
+                        }
+                    }
+                    for pickup in &mut self.pickups {
+                        pickup.update(speed, world_dt);
+                    }
+                    for coin in &mut self.coins {
+                        coin.update(speed, world_dt);
                     }
 
-                    // Update ground displacement
-                    self.ground_displacement += speed * dt;
-                    if self.ground_displacement >= screen_width() {
-                        self.ground_displacement -= screen_width();
+                    // Magnet pulls edible obstacles towards the dino's mouth, only while it's
+                    // eating; otherwise it would steer them into the dino and hurt it
+                    if self.dino.has_status(Status::Magnet) && self.dino.is_eating() {
+                        let mouth = self.dino.get_hitbox().point();
+                        for obstacle in &mut self.obstacles {
+                            obstacle.attract(mouth, MAGNET_STRENGTH, world_dt);
+                        }
                     }
 
+                    // Update terrain, scenery, biome, time of day and weather
+                    self.terrain.update(speed, world_dt);
+                    for parallax in &mut self.parallax {
+                        parallax.update(speed, world_dt);
+                    }
+                    self.biomes.update(self.score, world_dt);
+                    self.day_cycle.update(world_dt);
+                    self.weather.update(world_dt);
+
                     // Remove off-screen obstacles and finished hit animations
                     self.obstacles.retain(|o| !o.is_off_screen() && !o.should_remove());
+                    self.projectiles.retain(|p| !p.is_expired());
+                    self.pickups.retain(|p| !p.is_off_screen() && !p.is_collected());
+                    self.coins.retain(|c| !c.is_off_screen() && !c.is_collected());
+                    if self.boss.as_ref().is_some_and(|b| b.is_gone()) {
+                        self.boss = None;
+                    }
 
                     // Spawn new obstacles (only if dino is actually alive, not just in death animation)
                     if !self.dino.is_dead() {
-                        self.spawn_timer -= dt;
-                        if self.spawn_timer <= 0.0 {
-                            self.spawn_obstacle();
-                            let min_time = (OBSTACLE_SPAWN_TIME - self.game_speed_percent).max(0.5);
-                            self.spawn_timer = rand::gen_range(min_time, min_time + 1.0);
+                        self.director.update(&mut self.weather, self.score, world_dt);
+
+                        // Boss fights take over from regular spawning
+                        if self.boss.is_none() && self.score >= self.next_boss_score {
+                            self.boss = Some(Boss::new(self.obstacle_registry.assets().texture(Boss::TEXTURE)));
+                            self.next_boss_score += BOSS_SCORE_INTERVAL;
+                        }
+                        if self.boss.is_none() {
+                            self.spawn_timer -= world_dt;
+                            if self.spawn_timer <= 0.0 {
+                                rand::srand(self.rng_seed);
+                                if self.spawn_obstacle() {
+                                    let min_time = (OBSTACLE_SPAWN_TIME - self.game_speed_percent).max(0.5);
+                                    self.spawn_timer = rand::gen_range(min_time, min_time + 1.0);
+                                    self.rng_seed = rand::rand() as u64;
+                                } else {
+                                    // Same seed next time, so the same obstacle is tried again
+                                    self.spawn_timer = 0.1;
+                                }
+                            }
                         }
 
                         // Check collisions and apply effects
                         let dino_hitbox = self.dino.get_hitbox();
+                        let boss_was_defeated = self.boss.as_ref().is_some_and(|b| b.is_defeated());
+                        let health_before = self.dino.health;
+                        // Smash small obstacles around a landed ground slam
+                        if self.dino.take_slam_impact() {
+                            let area = self.dino.slam_area();
+                            self.obstacles.retain(|o| !(o.traits().small && area.overlaps(&o.get_hitbox())));
+                        }
                         for obstacle in &mut self.obstacles {
-                            if dino_hitbox.overlaps_rect(&obstacle.get_hitbox()) {
-                                let effect = obstacle.get_collision_effect(&self.dino);
+                            match contact(&dino_hitbox, &obstacle.get_hitbox()) {
+                                // Dashing passes straight through
+                                _ if self.dino.is_dashing() => {}
+                                // Landing on a stompable obstacle hits it instead of the dino
+                                Some(Contact::Top) if self.dino.is_falling() && obstacle.traits().stompable => {
+                                    events.push((ScoreEvent::Stomp, obstacle.get_hitbox().center()));
+                                    obstacle.on_hit();
+                                    self.dino.bounce();
+                                }
+                                Some(_) => {
+                                    if obstacle.traits().edible && self.dino.is_eating() {
+                                        events.push((ScoreEvent::AteFly, obstacle.get_hitbox().center()));
+                                    }
+                                    let effect = obstacle.get_collision_effect(&self.dino);
+                                    self.dino.apply_effect(effect);
+                                    if let Some(burst) = (obstacle.traits().hit_burst)(&self.dino) {
+                                        self.particles.emit(burst, obstacle.get_hitbox().center());
+                                    }
+                                    obstacle.on_hit();
+                                }
+                                None => {}
+                            }
+                        }
+                        for projectile in &mut self.projectiles {
+                            if dino_hitbox.overlaps_rect(&projectile.get_hitbox()) {
+                                // Boss rocks can be eaten and hurt the boss instead
+                                if projectile.is_edible() && self.dino.is_eating() {
+                                    let effect = projectile.eat();
+                                    self.dino.apply_effect(effect);
+                                    if let Some(boss) = &mut self.boss {
+                                        boss.take_hit();
+                                        events.push((ScoreEvent::BossHit, projectile.get_hitbox().center()));
+                                    }
+                                } else {
+                                    let effect = projectile.hit();
+                                    self.dino.apply_effect(effect);
+                                }
+                            }
+                        }
+                        if let Some(boss) = &mut self.boss {
+                            // Landing on its back hurts it; bumping into the body hurts the dino
+                            if self.dino.is_falling() && contact(&dino_hitbox, &boss.weak_point()) == Some(Contact::Top) {
+                                boss.take_hit();
+                                self.dino.bounce();
+                                events.push((ScoreEvent::BossHit, boss.weak_point().center()));
+                            } else if dino_hitbox.overlaps_rect(&boss.get_hitbox()) {
+                                self.dino.apply_effect(Effect::Damage(0.2));
+                            }
+                            if boss.is_defeated() && !boss_was_defeated {
+                                self.score += BOSS_REWARD;
+                                self.dino.apply_effect(Effect::RefillMana);
+                            }
+                        }
+                        for pickup in &mut self.pickups {
+                            if dino_hitbox.overlaps_rect(&pickup.get_hitbox()) {
+                                let effect = pickup.collect();
                                 self.dino.apply_effect(effect);
-                                obstacle.on_hit();
+                            }
+                        }
+                        for coin in &mut self.coins {
+                            if dino_hitbox.overlaps_rect(&coin.get_hitbox()) {
+                                coin.collect();
+                                self.run_coins += 1;
                             }
                         }
 
-                        // Update score and speed
-                        self.score += 1;
+                        // Score events feed the combo; any hit breaks it
+                        let bonus = self.dino.score_multiplier();
+                        for (event, position) in events {
+                            self.score += self.combo.register(event, position, bonus);
+                            self.stats.record(event);
+                        }
+                        if self.dino.health < health_before {
+                            self.combo.break_combo();
+                            self.camera.add_trauma(if self.dino.is_dead() { DEATH_TRAUMA } else { HIT_TRAUMA });
+                        }
+                        self.combo.update(dt);
+
+                        // Score the distance travelled, so it doesn't depend on the frame rate
+                        let distance = speed * world_dt / screen_width();
+                        self.score_progress += distance * SCORE_PER_SCREEN * self.dino.score_multiplier() as f32;
+                        let whole_points = self.score_progress.floor();
+                        self.score += whole_points as u32;
+                        self.score_progress -= whole_points;
+
+                        // Update speed
                         self.game_speed_percent += GAME_SPEED_INCREMENT * dt;
+
+                        // Remember this moment so the player can rewind to it
+                        if !self.dino.is_dead() && self.rewind.sample_due(dt) {
+                            let snapshot = self.snapshot();
+                            self.rewind.push(snapshot);
+                        }
                     }
                 }
 
-                // Always update clouds
-                for cloud in &mut self.clouds {
-                    cloud.update(dt);
-                }
+                // Always update clouds, and particles even once the world has stopped
+                self.update_clouds(dt);
+                let particle_speed = if should_update_world { self.game_speed() * self.dino.speed_multiplier() } else { 0.0 };
+                self.particles.update(particle_speed, dt);
 
                 // Check if dino is dead and animation finished
                 if self.dino.is_dead() && self.dino.death_animation_finished() {
@@ -193,54 +653,127 @@ impl Game {
                     if self.score > self.high_score {
                         self.high_score = self.score;
                     }
+                    self.profile.record_score(self.run_started_at, self.score);
+                    self.award_run_coins();
                 }
             }
             GameState::GameOver => {
-                for cloud in &mut self.clouds {
-                    cloud.update(dt);
-                }
+                self.update_clouds(dt);
 
                 if is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::R) {
                     self.reset();
+                } else if is_key_down(KeyCode::Backspace) && self.can_rewind() {
+                    self.state = GameState::Playing;
+                    self.rewind_step(dt);
+                }
+            }
+            GameState::Shop => {
+                self.update_clouds(dt);
+
+                if self.shop.update(&mut self.profile) {
+                    self.profile.write();
+                    self.dino.set_tuning(self.profile.dino_tuning());
                 }
+                if is_key_pressed(KeyCode::S) || is_key_pressed(KeyCode::Backspace) {
+                    self.state = GameState::Ready;
+                }
+            }
+        }
+    }
+
+    // Best runs in the top-right corner; runs under older scoring rules are marked
+    fn draw_leaderboard(&self) {
+        let font_size = screen_height() * 0.03;
+        let right = screen_width() * 0.98;
+        let mut y = font_size * 1.5;
+
+        let title = "Best runs";
+        let title_width = measure_text(title, None, font_size as u16, 1.0).width;
+        draw_text(title, right - title_width, y, font_size, DARKGRAY);
+        for (i, entry) in self.profile.leaderboard.iter().take(5).enumerate() {
+            y += font_size * 1.2;
+            let line = if entry.scoring_version == SCORING_VERSION {
+                format!("{}. {}", i + 1, entry.score)
+            } else {
+                format!("{}. {} (v{})", i + 1, entry.score, entry.scoring_version)
+            };
+            let line_width = measure_text(&line, None, font_size as u16, 1.0).width;
+            draw_text(&line, right - line_width, y, font_size, GRAY);
+        }
+    }
+
+    // Scenery of the biome being left behind, with the new one fading in over it
+    fn draw_parallax(&self, foreground: bool) {
+        let blend = self.biomes.blend();
+        let mut sets = vec![(self.biomes.current(), blend)];
+        if blend < 1.0 {
+            sets.insert(0, (self.biomes.previous(), 1.0));
+        }
+        for (biome, alpha) in sets {
+            let parallax = &self.parallax[biome.index()];
+            if foreground {
+                parallax.draw_foreground(alpha);
+            } else {
+                parallax.draw_background(alpha);
             }
         }
     }
 
     fn draw(&self) {
-        clear_background(SKYBLUE);
+        let canvas_camera = self.canvas.camera();
+        set_camera(&canvas_camera);
+        clear_background(self.day_cycle.sky_color());
+        let world_camera = self.camera.world_camera(&self.canvas);
+        set_camera(&world_camera);
+        self.day_cycle.draw_sky();
 
         // Draw clouds
+        let (sky, biome) = (self.day_cycle.cloud_tint(), self.biomes.cloud_tint());
+        let cloud_tint = Color::new(sky.r * biome.r, sky.g * biome.g, sky.b * biome.b, 1.0);
         for cloud in &self.clouds {
-            cloud.draw();
+            cloud.draw(cloud_tint);
         }
 
-        // Draw ground (at 85% of screen height)
-        let ground = ground_y();
-        draw_rectangle(0.0, ground, screen_width(), screen_height() - ground, BEIGE);
-        draw_line(0.0, ground, screen_width(), ground, 2.0, DARKBROWN);
-
-        // Draw ground texture (scaled)
-        let pebble_size = screen_height() * 0.006;
-        let spacing = screen_width() * 0.025;
-        for i in 0..((screen_width() / spacing) as i32 + 1) {
-            let x = (i as f32 * spacing + self.ground_displacement) % screen_width();
-            if rand::gen_range(0, 5) == 0 {
-                draw_rectangle(screen_width() - x, ground + pebble_size, pebble_size, pebble_size, GRAY);
-            }
-        }
+        // Distant scenery, then the ground with its pits, hills, ledges and details
+        self.draw_parallax(false);
+        self.terrain.draw(self.biomes.ground_color());
 
         // Draw obstacles
         for obstacle in &self.obstacles {
             obstacle.draw();
         }
 
+        for projectile in &self.projectiles {
+            projectile.draw();
+        }
+        for pickup in &self.pickups {
+            pickup.draw();
+        }
+        for coin in &self.coins {
+            coin.draw();
+        }
+        if let Some(boss) = &self.boss {
+            boss.draw();
+        }
+
         // Draw dino
-        if self.state == GameState::Ready {
+        if self.state == GameState::Ready || self.state == GameState::Shop {
             self.dino.draw_stand();
         } else {
             self.dino.draw();
         }
+        self.particles.draw();
+        self.draw_parallax(true);
+
+        // Weather and night cover the whole view, measured from where the dino appears on screen
+        set_camera(&canvas_camera);
+        let dino_front = GameCamera::world_to_canvas(&world_camera, Vec2::new(self.dino.x() + self.dino.width(), 0.0)).x;
+        self.weather.draw(dino_front);
+        // Night falls over the world, but not over the HUD
+        self.day_cycle.draw_darkness(dino_front);
+        set_camera(&world_camera);
+        self.combo.draw_popups();
+        set_camera(&canvas_camera);
 
         // Draw dino stats below ground
         self.dino.draw_stats();
@@ -250,7 +783,10 @@ impl Game {
         let font_small = screen_height() * 0.035;
         let margin = screen_width() * 0.02;
 
-        draw_text(&format!("Score: {}", self.score), margin, font_large * 1.2, font_large, DARKGRAY);
+        let score_text = format!("Score: {}", self.score);
+        draw_text(&score_text, margin, font_large * 1.2, font_large, DARKGRAY);
+        let score_width = measure_text(&score_text, None, font_large as u16, 1.0).width;
+        self.combo.draw_multiplier(margin + score_width + font_large * 0.5, font_large * 1.2, font_large);
         draw_text(
             &format!("High Score: {}", self.high_score),
             margin,
@@ -258,10 +794,45 @@ impl Game {
             font_small,
             GRAY,
         );
+        draw_text(
+            &format!("Rewinds: {}", self.rewind_charges),
+            margin,
+            font_large * 1.2 + font_small * 2.4,
+            font_small,
+            GRAY,
+        );
+        let coins_text = match self.state {
+            GameState::Playing => format!("Coins: {} (+{})", self.profile.coins, self.run_coins),
+            _ => format!("Coins: {}", self.profile.coins),
+        };
+        draw_text(&coins_text, margin, font_large * 1.2 + font_small * 3.6, font_small, GOLD);
+
+        if let Some(boss) = &self.boss {
+            boss.draw_health_bar();
+        }
+        if self.state == GameState::Playing {
+            self.weather.draw_indicator();
+            self.biomes.draw_banner();
+        }
+
+        // Brief white flash while a near miss slows things down
+        if self.slow_motion_timer > 0.0 {
+            let alpha = 0.25 * self.slow_motion_timer / NEAR_MISS_SLOW_MOTION;
+            draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(1.0, 1.0, 1.0, alpha));
+        }
+
+        if self.is_rewinding {
+            draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.2, 0.2, 0.5, 0.15));
+            let text = "<< REWIND";
+            let text_width = measure_text(text, None, font_large as u16, 1.0).width;
+            draw_text(text, (screen_width() - text_width) / 2.0, screen_height() * 0.2, font_large, DARKBLUE);
+        }
 
         // Draw state-specific UI
         match self.state {
             GameState::Ready => {
+                self.draw_leaderboard();
+
                 let font_title = screen_height() * 0.06;
                 let font_sub = screen_height() * 0.03;
                 let center_x = screen_width() / 2.0;
@@ -277,11 +848,26 @@ impl Game {
                     DARKGRAY,
                 );
 
+                if let Some(save) = &self.saved_run {
+                    let text = format!("Press C to continue (score {})", save.world.score);
+                    let text_width = measure_text(&text, None, font_sub as u16, 1.0).width;
+                    draw_text(
+                        &text,
+                        center_x - text_width / 2.0,
+                        start_y - font_title,
+                        font_sub,
+                        DARKGREEN,
+                    );
+                }
+
                 let controls = [
                     "SPACE / UP - Jump (double jump costs mana)",
                     "DOWN - Duck",
                     "F - Fly (hold while jumping, costs mana)",
                     "E - Eat (catch flies to restore mana)",
+                    "SHIFT - Dash / X - Ground slam (cost mana)",
+                    "BACKSPACE - Rewind (hold, limited uses)",
+                    "S - Shop (spend coins on upgrades)",
                 ];
                 for (i, line) in controls.iter().enumerate() {
                     let line_width = measure_text(line, None, font_sub as u16, 1.0).width;
@@ -295,6 +881,7 @@ impl Game {
                 }
             }
             GameState::GameOver => {
+                self.draw_leaderboard();
                 let font_title = screen_height() * 0.1;
                 let font_sub = screen_height() * 0.04;
                 let font_controls = screen_height() * 0.025;
@@ -310,7 +897,11 @@ impl Game {
                     RED,
                 );
 
-                let restart = "Press SPACE or R to restart";
+                let restart = if self.rewind_charges > 0 {
+                    "Press SPACE or R to restart, hold BACKSPACE to rewind"
+                } else {
+                    "Press SPACE or R to restart"
+                };
                 let restart_width = measure_text(restart, None, font_sub as u16, 1.0).width;
                 draw_text(
                     restart,
@@ -320,33 +911,79 @@ impl Game {
                     DARKGRAY,
                 );
 
+                let reward = format!("+{} coins this run", self.coins_awarded);
+                let reward_width = measure_text(&reward, None, font_sub as u16, 1.0).width;
+                draw_text(
+                    &reward,
+                    center_x - reward_width / 2.0,
+                    screen_height() * 0.5,
+                    font_sub,
+                    GOLD,
+                );
+
+                let stats = format!(
+                    "Near misses: {}   Stomps: {}   Flies eaten: {}   Obstacles cleared: {}",
+                    self.stats.near_misses, self.stats.stomps, self.stats.flies_eaten, self.stats.obstacles_cleared,
+                );
+                let stats_width = measure_text(&stats, None, font_controls as u16, 1.0).width;
+                draw_text(
+                    &stats,
+                    center_x - stats_width / 2.0,
+                    screen_height() * 0.54,
+                    font_controls,
+                    DARKGRAY,
+                );
+
                 let controls = [
                     "SPACE / UP - Jump (double jump costs mana)",
                     "DOWN - Duck",
                     "F - Fly (hold while jumping, costs mana)",
                     "E - Eat (catch flies to restore mana)",
+                    "SHIFT - Dash / X - Ground slam (cost mana)",
+                    "BACKSPACE - Rewind (hold, limited uses)",
                 ];
                 for (i, line) in controls.iter().enumerate() {
                     let line_width = measure_text(line, None, font_controls as u16, 1.0).width;
                     draw_text(
                         line,
                         center_x - line_width / 2.0,
-                        screen_height() * 0.55 + (i as f32) * font_controls * 1.3,
+                        screen_height() * 0.6 + (i as f32) * font_controls * 1.3,
                         font_controls,
                         GRAY,
                     );
                 }
             }
+            GameState::Shop => {
+                self.shop.draw(&self.profile);
+            }
             GameState::Playing => {}
         }
+
+        self.canvas.present();
     }
 }
 
-#[macroquad::main("Dino Run")]
+fn window_conf() -> Conf {
+    Conf {
+        window_title: "Dino Run".to_string(),
+        window_width: canvas::WIDTH as i32,
+        window_height: canvas::HEIGHT as i32,
+        window_resizable: true,
+        ..Default::default()
+    }
+}
+
+#[macroquad::main(window_conf)]
 async fn main() {
     let mut game = Game::new().await;
+    // Intercept window close so the current run can be saved first
+    prevent_quit();
 
     loop {
+        if is_quit_requested() || is_key_pressed(KeyCode::Escape) {
+            game.save_on_quit();
+            break;
+        }
         game.update().await;
         game.draw();
         next_frame().await;
@@ -0,0 +1,192 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::parallax::{LayerDef, DESERT_LAYERS, JUNGLE_LAYERS, TUNDRA_LAYERS, VOLCANO_LAYERS};
+use super::{Boulder, Burrower, CactusKind, Fly, FlyerKind, Hopper, RockKind, Spitter, Thrower};
+
+// A new biome every this many points
+const BIOME_SCORE_INTERVAL: u32 = 2500;
+// Seconds to blend from one biome into the next
+const TRANSITION_TIME: f32 = 4.0;
+
+const DESERT_OBSTACLES: &[&str] = &[
+    RockKind::Stone.id(), CactusKind::Cactus.id(), Fly::ID, Boulder::ID,
+    Burrower::ID, Hopper::ID, FlyerKind::Diver.id(), Spitter::ID,
+];
+const JUNGLE_OBSTACLES: &[&str] = &[
+    RockKind::Stone.id(), Fly::ID, Hopper::ID, Burrower::ID,
+    FlyerKind::Swooper.id(), FlyerKind::Charger.id(), Spitter::ID, Thrower::ID,
+];
+const TUNDRA_OBSTACLES: &[&str] = &[
+    RockKind::Ice.id(), Fly::ID, Boulder::ID, Burrower::ID,
+    FlyerKind::Swooper.id(), FlyerKind::Diver.id(), Thrower::ID,
+];
+const VOLCANO_OBSTACLES: &[&str] = &[
+    RockKind::Stone.id(), CactusKind::LavaVent.id(), Fly::ID, Boulder::ID,
+    Hopper::ID, FlyerKind::Diver.id(), FlyerKind::Charger.id(), Thrower::ID,
+];
+
+/// Regions the run passes through, in order; each has its own scenery and obstacles
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Biome {
+    Desert,
+    Jungle,
+    Tundra,
+    Volcano,
+}
+
+impl Biome {
+    pub const ALL: [Biome; 4] = [Biome::Desert, Biome::Jungle, Biome::Tundra, Biome::Volcano];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Biome::Desert => "Desert",
+            Biome::Jungle => "Jungle",
+            Biome::Tundra => "Tundra",
+            Biome::Volcano => "Volcano",
+        }
+    }
+
+    pub fn layers(self) -> &'static [LayerDef] {
+        match self {
+            Biome::Desert => DESERT_LAYERS,
+            Biome::Jungle => JUNGLE_LAYERS,
+            Biome::Tundra => TUNDRA_LAYERS,
+            Biome::Volcano => VOLCANO_LAYERS,
+        }
+    }
+
+    fn ground_color(self) -> Color {
+        match self {
+            Biome::Desert => BEIGE,
+            Biome::Jungle => Color::new(0.45, 0.33, 0.2, 1.0),
+            Biome::Tundra => Color::new(0.9, 0.93, 0.97, 1.0),
+            Biome::Volcano => Color::new(0.3, 0.24, 0.23, 1.0),
+        }
+    }
+
+    // Range of sprites in the cloud sheet to pick from (start, end)
+    fn cloud_sprites(self) -> (usize, usize) {
+        match self {
+            // Thin wisps
+            Biome::Desert => (0, 2),
+            Biome::Jungle => (0, 4),
+            // Heavy snow clouds, and ash clouds over the volcano
+            Biome::Tundra | Biome::Volcano => (2, 4),
+        }
+    }
+
+    fn cloud_tint(self) -> Color {
+        match self {
+            Biome::Desert => Color::new(1.0, 0.95, 0.85, 1.0),
+            Biome::Jungle => WHITE,
+            Biome::Tundra => Color::new(0.9, 0.95, 1.0, 1.0),
+            Biome::Volcano => Color::new(0.45, 0.4, 0.4, 1.0),
+        }
+    }
+
+    /// Registry ids of the obstacles that can spawn here
+    pub fn obstacles(self) -> &'static [&'static str] {
+        match self {
+            Biome::Desert => DESERT_OBSTACLES,
+            Biome::Jungle => JUNGLE_OBSTACLES,
+            Biome::Tundra => TUNDRA_OBSTACLES,
+            Biome::Volcano => VOLCANO_OBSTACLES,
+        }
+    }
+}
+
+/// Which biome the run is in, and how far the blend from the last one has got
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Biomes {
+    current: Biome,
+    previous: Biome,
+    // 0..1 from the previous biome to the current one
+    blend: f32,
+    next_score: u32,
+}
+
+impl Biomes {
+    pub fn new() -> Self {
+        Self {
+            current: Biome::Desert,
+            previous: Biome::Desert,
+            blend: 1.0,
+            next_score: BIOME_SCORE_INTERVAL,
+        }
+    }
+
+    pub fn update(&mut self, score: u32, dt: f32) {
+        if score >= self.next_score {
+            self.previous = self.current;
+            self.current = self.current.next();
+            self.blend = 0.0;
+            self.next_score += BIOME_SCORE_INTERVAL;
+        }
+        self.blend = (self.blend + dt / TRANSITION_TIME).min(1.0);
+    }
+
+    pub fn current(&self) -> Biome {
+        self.current
+    }
+
+    pub fn previous(&self) -> Biome {
+        self.previous
+    }
+
+    pub fn blend(&self) -> f32 {
+        self.blend
+    }
+
+    pub fn ground_color(&self) -> Color {
+        mix(self.previous.ground_color(), self.current.ground_color(), self.blend)
+    }
+
+    pub fn cloud_tint(&self) -> Color {
+        mix(self.previous.cloud_tint(), self.current.cloud_tint(), self.blend)
+    }
+
+    /// Cloud sprites for clouds coming on screen now
+    pub fn cloud_sprites(&self) -> (usize, usize) {
+        self.current.cloud_sprites()
+    }
+
+    pub fn obstacles(&self) -> &'static [&'static str] {
+        self.current.obstacles()
+    }
+
+    /// Name of the new biome, fading out as the transition completes
+    pub fn draw_banner(&self) {
+        if self.blend >= 1.0 {
+            return;
+        }
+        let alpha = (1.0 - self.blend) * 2.0;
+        let font_size = screen_height() * 0.06;
+        let text = format!("Entering the {}", self.current.name());
+        let width = measure_text(&text, None, font_size as u16, 1.0).width;
+        draw_text(
+            &text,
+            (screen_width() - width) / 2.0,
+            screen_height() * 0.28,
+            font_size,
+            Color { a: alpha.min(1.0), ..DARKGRAY },
+        );
+    }
+}
+
+fn mix(from: Color, to: Color, t: f32) -> Color {
+    Color::new(
+        from.r + (to.r - from.r) * t,
+        from.g + (to.g - from.g) * t,
+        from.b + (to.b - from.b) * t,
+        from.a + (to.a - from.a) * t,
+    )
+}
@@ -0,0 +1,270 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::projectile::{Projectile, ProjectileKind};
+
+const SPRITE_COLS: usize = 2;
+const SPRITE_ROWS: usize = 2;
+const FRAME_COUNT: usize = 4;
+const ANIMATION_SPEED: f32 = 0.2;
+const MAX_HEALTH: u32 = 5;
+// Where it hangs around between attacks (screen percentages)
+const HOVER_X: f32 = 0.7;
+const HOVER_Y: f32 = 0.12;
+// Lowest point of a swoop: low enough to land on, high enough to run under
+const SWOOP_LOW_Y: f32 = 0.5;
+const HOVER_TIME: f32 = 1.5;
+const SWOOP_TIME: f32 = 2.0;
+const ROCKS_PER_VOLLEY: u32 = 3;
+const ROCK_INTERVAL: f32 = 0.6;
+const ROCK_FLIGHT_TIME: f32 = 0.9;
+const HURT_FLASH_TIME: f32 = 0.3;
+const TINT: Color = Color::new(0.7, 0.5, 0.9, 1.0);
+
+/// Attack state machine
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum BossPhase {
+    Entering,
+    Hovering,
+    // Dives down towards the dino's column and climbs back up
+    Swooping { from_x: f32, to_x: f32 },
+    DroppingRocks { rocks_left: u32 },
+    Retreating,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BossState {
+    x_percent: f32,
+    y_percent: f32,
+    health: u32,
+    phase: BossPhase,
+    phase_timer: f32,
+    next_attack_is_swoop: bool,
+    hurt_timer: f32,
+    current_frame: usize,
+    animation_timer: f32,
+}
+
+/// Giant pterodactyl that shows up at score milestones
+pub struct Boss {
+    state: BossState,
+    size_percent: f32,
+    // Latest dino position, in screen percentages
+    target: (f32, f32),
+    pending: Vec<Projectile>,
+    texture: Texture2D,
+}
+
+impl Boss {
+    // Shared with the flying obstacles, loaded through the obstacle registry
+    pub const TEXTURE: &'static str = "assets/bird.png";
+
+    pub fn new(texture: Texture2D) -> Self {
+        Self::restore(
+            BossState {
+                x_percent: 1.2,
+                y_percent: HOVER_Y,
+                health: MAX_HEALTH,
+                phase: BossPhase::Entering,
+                phase_timer: 0.0,
+                // Opens with a rock volley, then alternates
+                next_attack_is_swoop: false,
+                hurt_timer: 0.0,
+                current_frame: 0,
+                animation_timer: 0.0,
+            },
+            texture,
+        )
+    }
+
+    pub fn restore(state: BossState, texture: Texture2D) -> Self {
+        Self {
+            state,
+            size_percent: 0.3,
+            target: (0.0, 0.0),
+            pending: Vec::new(),
+            texture,
+        }
+    }
+
+    pub fn snapshot(&self) -> BossState {
+        self.state.clone()
+    }
+
+    fn x(&self) -> f32 {
+        screen_width() * self.state.x_percent
+    }
+
+    fn y(&self) -> f32 {
+        screen_height() * self.state.y_percent
+    }
+
+    fn size(&self) -> f32 {
+        screen_height() * self.size_percent
+    }
+
+    fn enter_phase(&mut self, phase: BossPhase) {
+        self.state.phase = phase;
+        self.state.phase_timer = 0.0;
+    }
+
+    pub fn track_target(&mut self, target: Vec2) {
+        self.target = (target.x / screen_width(), target.y / screen_height());
+    }
+
+    pub fn update(&mut self, scroll_speed: f32, dt: f32) {
+        let state = &mut self.state;
+        state.phase_timer += dt;
+        state.hurt_timer = (state.hurt_timer - dt).max(0.0);
+
+        state.animation_timer += dt;
+        if state.animation_timer >= ANIMATION_SPEED {
+            state.animation_timer = 0.0;
+            state.current_frame = (state.current_frame + 1) % FRAME_COUNT;
+        }
+
+        match state.phase {
+            BossPhase::Entering => {
+                state.x_percent -= 0.3 * dt;
+                if state.x_percent <= HOVER_X {
+                    state.x_percent = HOVER_X;
+                    self.enter_phase(BossPhase::Hovering);
+                }
+            }
+            BossPhase::Hovering => {
+                state.y_percent = HOVER_Y + (state.phase_timer * 3.0).sin() * 0.02;
+                if state.phase_timer >= HOVER_TIME {
+                    let attack = if state.next_attack_is_swoop {
+                        // Aim the middle of the body at the dino's column
+                        let to_x = self.target.0 - self.size_percent * screen_height() / screen_width() / 2.0;
+                        BossPhase::Swooping { from_x: HOVER_X, to_x }
+                    } else {
+                        BossPhase::DroppingRocks { rocks_left: ROCKS_PER_VOLLEY }
+                    };
+                    state.next_attack_is_swoop = !state.next_attack_is_swoop;
+                    self.enter_phase(attack);
+                }
+            }
+            BossPhase::Swooping { from_x, to_x } => {
+                let t = (state.phase_timer / SWOOP_TIME).min(1.0);
+                let arc = (t * std::f32::consts::PI).sin();
+                state.x_percent = from_x + (to_x - from_x) * arc;
+                state.y_percent = HOVER_Y + (SWOOP_LOW_Y - HOVER_Y) * arc;
+                if t >= 1.0 {
+                    self.enter_phase(BossPhase::Hovering);
+                }
+            }
+            BossPhase::DroppingRocks { rocks_left } => {
+                if state.phase_timer >= ROCK_INTERVAL {
+                    state.phase_timer = 0.0;
+                    let claws = (
+                        state.x_percent + self.size_percent * screen_height() / screen_width() / 2.0,
+                        state.y_percent + self.size_percent * 0.8,
+                    );
+                    let scroll_percent = scroll_speed / screen_width();
+                    let (velocity_x, velocity_y) = Projectile::aimed_velocity(
+                        ProjectileKind::BossRock, claws, self.target, scroll_percent, ROCK_FLIGHT_TIME,
+                    );
+                    self.pending.push(Projectile::new(ProjectileKind::BossRock, claws.0, claws.1, velocity_x, velocity_y));
+                    state.phase = if rocks_left > 1 {
+                        BossPhase::DroppingRocks { rocks_left: rocks_left - 1 }
+                    } else {
+                        BossPhase::Hovering
+                    };
+                }
+            }
+            BossPhase::Retreating => {
+                state.x_percent += 0.4 * dt;
+                state.y_percent -= 0.3 * dt;
+            }
+        }
+    }
+
+    pub fn take_projectiles(&mut self) -> Vec<Projectile> {
+        std::mem::take(&mut self.pending)
+    }
+
+    pub fn draw(&self) {
+        let frame_col = self.state.current_frame % SPRITE_COLS;
+        let frame_row = self.state.current_frame / SPRITE_COLS;
+        let frame_width = self.texture.width() / SPRITE_COLS as f32;
+        let frame_height = self.texture.height() / SPRITE_ROWS as f32;
+        let source_rect = Rect::new(
+            frame_col as f32 * frame_width,
+            frame_row as f32 * frame_height,
+            frame_width,
+            frame_height,
+        );
+
+        // Flash white when hurt
+        let color = if self.state.hurt_timer > 0.0 && (self.state.hurt_timer * 20.0) as u32 % 2 == 1 {
+            WHITE
+        } else {
+            TINT
+        };
+        draw_texture_ex(
+            &self.texture,
+            self.x(),
+            self.y(),
+            color,
+            DrawTextureParams {
+                source: Some(source_rect),
+                dest_size: Some(Vec2::new(self.size(), self.size())),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// HP bar across the top of the screen
+    pub fn draw_health_bar(&self) {
+        let width = screen_width() * 0.4;
+        let height = screen_height() * 0.025;
+        let x = (screen_width() - width) / 2.0;
+        let y = screen_height() * 0.04;
+        let fraction = self.state.health as f32 / MAX_HEALTH as f32;
+
+        draw_rectangle(x, y, width, height, LIGHTGRAY);
+        draw_rectangle(x, y, width * fraction, height, PURPLE);
+        draw_rectangle_lines(x, y, width, height, 2.0, DARKGRAY);
+
+        let label = "PTERODACTYL";
+        let font_size = height * 1.2;
+        let label_width = measure_text(label, None, font_size as u16, 1.0).width;
+        draw_text(label, (screen_width() - label_width) / 2.0, y - height * 0.3, font_size, DARKGRAY);
+    }
+
+    /// Body; touching it hurts
+    pub fn get_hitbox(&self) -> Rect {
+        if self.is_defeated() {
+            return Rect::new(0.0, 0.0, 0.0, 0.0);
+        }
+        Rect::new(self.x() + self.size() * 0.15, self.y() + self.size() * 0.35, self.size() * 0.7, self.size() * 0.3)
+    }
+
+    /// Strip along its back that can be stomped
+    pub fn weak_point(&self) -> Rect {
+        if self.is_defeated() {
+            return Rect::new(0.0, 0.0, 0.0, 0.0);
+        }
+        Rect::new(self.x() + self.size() * 0.25, self.y() + self.size() * 0.25, self.size() * 0.5, self.size() * 0.12)
+    }
+
+    pub fn take_hit(&mut self) {
+        if self.is_defeated() {
+            return;
+        }
+        self.state.health -= 1;
+        self.state.hurt_timer = HURT_FLASH_TIME;
+        if self.state.health == 0 {
+            self.enter_phase(BossPhase::Retreating);
+        }
+    }
+
+    pub fn is_defeated(&self) -> bool {
+        self.state.health == 0
+    }
+
+    /// Defeated and flown off screen
+    pub fn is_gone(&self) -> bool {
+        self.is_defeated() && (self.x() > screen_width() || self.y() + self.size() < 0.0)
+    }
+}
@@ -0,0 +1,133 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::obstacle::Obstacle;
+use super::registry::{ObstacleTraits, ObstacleType, SavedObstacle};
+use super::dino::{ground_y, Effect, Status};
+
+const TEXTURE: &str = "assets/rock.png";
+// Extra speed on top of the world scroll, as a fraction of it
+const ROLL_SPEED_BONUS: f32 = 0.4;
+
+#[derive(Serialize, Deserialize)]
+pub struct BoulderState {
+    pub x_percent: f32,
+    pub rotation: f32,
+    pub rise_percent: f32,
+}
+
+/// A rock that rolls towards the dino faster than the ground scrolls
+pub struct Boulder {
+    x_percent: f32,
+    size_percent: f32,
+    rotation: f32,
+    // Height of the ground under it above the base ground level, as percent of screen height
+    rise_percent: f32,
+    texture: Texture2D,
+}
+
+impl Boulder {
+    pub const ID: &'static str = "boulder";
+
+    pub fn obstacle_type() -> ObstacleType {
+        ObstacleType {
+            id: Self::ID,
+            assets: &[TEXTURE],
+            spawn_weight: 1,
+            traits: ObstacleTraits::default(),
+            spawn: |x, assets| Box::new(Boulder::new(x, assets.texture(TEXTURE))),
+            restore: |saved, assets| {
+                let state = saved.decode()?;
+                Some(Box::new(Boulder::restore(state, assets.texture(TEXTURE))))
+            },
+        }
+    }
+
+    pub fn new(x: f32, texture: Texture2D) -> Self {
+        Self {
+            x_percent: x / screen_width(),
+            size_percent: 0.1,
+            rotation: 0.0,
+            rise_percent: 0.0,
+            texture,
+        }
+    }
+
+    pub fn restore(state: BoulderState, texture: Texture2D) -> Self {
+        Self {
+            x_percent: state.x_percent,
+            size_percent: 0.1,
+            rotation: state.rotation,
+            rise_percent: state.rise_percent,
+            texture,
+        }
+    }
+
+    fn x(&self) -> f32 {
+        screen_width() * self.x_percent
+    }
+
+    fn y(&self) -> f32 {
+        ground_y() - screen_height() * self.rise_percent - self.size() + 4.0  // Adjust offset to sit on ground
+    }
+
+    fn size(&self) -> f32 {
+        screen_height() * self.size_percent
+    }
+}
+
+impl Obstacle for Boulder {
+    fn update(&mut self, speed: f32, dt: f32) {
+        let roll_speed = speed * (1.0 + ROLL_SPEED_BONUS);
+        self.x_percent -= (roll_speed / screen_width()) * dt;
+
+        // Roll without slipping: angle follows distance over radius
+        let radius = self.size() / 2.0;
+        self.rotation -= roll_speed * dt / radius;
+    }
+
+    fn set_ground(&mut self, surface: Option<f32>) {
+        // Keeps its height over pits, rolling across on momentum
+        if let Some(y) = surface {
+            self.rise_percent = (ground_y() - y) / screen_height();
+        }
+    }
+
+    fn draw(&self) {
+        draw_texture_ex(
+            &self.texture,
+            self.x(),
+            self.y(),
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(Vec2::new(self.size(), self.size())),
+                rotation: self.rotation,
+                ..Default::default()
+            },
+        );
+    }
+
+    fn get_hitbox(&self) -> Rect {
+        let inset = self.size() * 0.2;
+        Rect::new(self.x() + inset, self.y() + inset, self.size() - inset * 2.0, self.size() - inset)
+    }
+
+    fn is_off_screen(&self) -> bool {
+        self.x() + self.size() < 0.0
+    }
+
+    fn footprint(&self) -> Option<(f32, f32)> {
+        Some((self.x(), self.x() + self.size()))
+    }
+
+    fn get_collision_effect(&self, _dino: &crate::models::Dino) -> Effect {
+        Effect::Combined(vec![Effect::Damage(0.25), Effect::Status(Status::Stun, 0.3)])
+    }
+
+    fn snapshot(&self) -> SavedObstacle {
+        SavedObstacle::new(Self::ID, &BoulderState {
+            x_percent: self.x_percent,
+            rotation: self.rotation,
+            rise_percent: self.rise_percent,
+        })
+    }
+}
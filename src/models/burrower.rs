@@ -0,0 +1,180 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::obstacle::Obstacle;
+use super::registry::{ObstacleTraits, ObstacleType, SavedObstacle};
+use super::dino::{ground_y, Effect};
+
+// Surface cycle timings in seconds
+const HIDDEN_TIME: f32 = 1.0;
+const SURFACED_TIME: f32 = 0.8;
+const EMERGE_SPEED: f32 = 5.0;
+// How far out of the ground it has to be before it can hurt
+const DANGEROUS_EMERGENCE: f32 = 0.5;
+const BODY_COLOR: Color = Color::new(0.45, 0.3, 0.2, 1.0);
+const MOUND_COLOR: Color = Color::new(0.55, 0.4, 0.25, 1.0);
+
+#[derive(Serialize, Deserialize)]
+pub struct BurrowerState {
+    pub x_percent: f32,
+    pub phase_timer: f32,
+    pub is_surfaced: bool,
+    pub emergence: f32,
+    pub is_hit: bool,
+    pub rise_percent: f32,
+}
+
+/// Mole-like enemy that pops out of the ground on a timer
+pub struct Burrower {
+    x_percent: f32,
+    size_percent: f32,
+    phase_timer: f32,
+    is_surfaced: bool,
+    // 0.0 = fully underground, 1.0 = fully out
+    emergence: f32,
+    is_hit: bool,
+    // Height of the ground under it above the base ground level, as percent of screen height
+    rise_percent: f32,
+}
+
+impl Burrower {
+    pub const ID: &'static str = "burrower";
+
+    pub fn obstacle_type() -> ObstacleType {
+        ObstacleType {
+            id: Self::ID,
+            assets: &[],
+            spawn_weight: 1,
+            traits: ObstacleTraits { small: true, ..ObstacleTraits::default() },
+            spawn: |x, _| Box::new(Burrower::new(x)),
+            restore: |saved, _| {
+                let state = saved.decode()?;
+                Some(Box::new(Burrower::restore(state)))
+            },
+        }
+    }
+
+    pub fn new(x: f32) -> Self {
+        Self {
+            x_percent: x / screen_width(),
+            size_percent: 0.1,
+            phase_timer: rand::gen_range(0.0, HIDDEN_TIME),
+            is_surfaced: false,
+            emergence: 0.0,
+            is_hit: false,
+            rise_percent: 0.0,
+        }
+    }
+
+    pub fn restore(state: BurrowerState) -> Self {
+        Self {
+            x_percent: state.x_percent,
+            size_percent: 0.1,
+            phase_timer: state.phase_timer,
+            is_surfaced: state.is_surfaced,
+            emergence: state.emergence,
+            is_hit: state.is_hit,
+            rise_percent: state.rise_percent,
+        }
+    }
+
+    fn x(&self) -> f32 {
+        screen_width() * self.x_percent
+    }
+
+    fn size(&self) -> f32 {
+        screen_height() * self.size_percent
+    }
+
+    fn ground(&self) -> f32 {
+        ground_y() - screen_height() * self.rise_percent
+    }
+
+    // Top of the visible body, rising out of the ground as it emerges
+    fn body_top(&self) -> f32 {
+        self.ground() - self.size() * self.emergence
+    }
+}
+
+impl Obstacle for Burrower {
+    fn update(&mut self, speed: f32, dt: f32) {
+        let speed_percent = speed / screen_width();
+        self.x_percent -= speed_percent * dt;
+
+        self.phase_timer += dt;
+        let phase_time = if self.is_surfaced { SURFACED_TIME } else { HIDDEN_TIME };
+        if self.phase_timer >= phase_time {
+            self.phase_timer = 0.0;
+            // Once hit it stays underground
+            self.is_surfaced = !self.is_surfaced && !self.is_hit;
+        }
+
+        let target = if self.is_surfaced { 1.0 } else { 0.0 };
+        let step = EMERGE_SPEED * dt;
+        self.emergence += (target - self.emergence).clamp(-step, step);
+    }
+
+    fn set_ground(&mut self, surface: Option<f32>) {
+        if let Some(y) = surface {
+            self.rise_percent = (ground_y() - y) / screen_height();
+        }
+    }
+
+    fn draw(&self) {
+        let center_x = self.x() + self.size() / 2.0;
+        let ground = self.ground();
+
+        if self.emergence > 0.0 {
+            let body_height = self.size() * self.emergence;
+            let color = if self.is_hit { GRAY } else { BODY_COLOR };
+            draw_rectangle(self.x() + self.size() * 0.15, self.body_top() + self.size() * 0.2, self.size() * 0.7, body_height, color);
+            draw_circle(center_x, self.body_top() + self.size() * 0.3, self.size() * 0.35, color);
+
+            // Squinting eyes and a pink nose
+            let eye_y = self.body_top() + self.size() * 0.25;
+            draw_line(center_x - self.size() * 0.2, eye_y, center_x - self.size() * 0.08, eye_y, 2.0, BLACK);
+            draw_line(center_x + self.size() * 0.08, eye_y, center_x + self.size() * 0.2, eye_y, 2.0, BLACK);
+            draw_circle(center_x - self.size() * 0.3, self.body_top() + self.size() * 0.4, self.size() * 0.07, PINK);
+        }
+
+        // Dirt mound it digs out of
+        draw_ellipse(center_x, ground, self.size() * 0.6, self.size() * 0.15, 0.0, MOUND_COLOR);
+    }
+
+    fn get_hitbox(&self) -> Rect {
+        // Harmless while underground or after being hit
+        if self.is_hit || self.emergence < DANGEROUS_EMERGENCE {
+            Rect::new(0.0, 0.0, 0.0, 0.0)
+        } else {
+            Rect::new(self.x() + self.size() * 0.15, self.body_top(), self.size() * 0.7, self.ground() - self.body_top())
+        }
+    }
+
+    fn is_off_screen(&self) -> bool {
+        self.x() + self.size() < 0.0
+    }
+
+    fn footprint(&self) -> Option<(f32, f32)> {
+        Some((self.x(), self.x() + self.size()))
+    }
+
+    fn get_collision_effect(&self, _dino: &crate::models::Dino) -> Effect {
+        Effect::Damage(0.2)
+    }
+
+    fn snapshot(&self) -> SavedObstacle {
+        SavedObstacle::new(Self::ID, &BurrowerState {
+            x_percent: self.x_percent,
+            phase_timer: self.phase_timer,
+            is_surfaced: self.is_surfaced,
+            emergence: self.emergence,
+            is_hit: self.is_hit,
+            rise_percent: self.rise_percent,
+        })
+    }
+
+    fn on_hit(&mut self) {
+        self.is_hit = true;
+        self.is_surfaced = false;
+        self.phase_timer = 0.0;
+    }
+}
@@ -1,5 +1,8 @@
-use macroquad::prelude::*;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
 use super::obstacle::Obstacle;
+use super::particles::Burst;
+use super::registry::{ObstacleAssets, ObstacleTraits, ObstacleType, SavedObstacle};
 use super::dino::{ground_y, Effect};
 
 const SPRITE_COLS: usize = 2;
@@ -7,7 +10,63 @@ const SPRITE_ROWS: usize = 2;
 const FRAME_COUNT: usize = 4;
 const HIT_FRAME_SPEED: f32 = 0.15;
 
+const TEXTURE: &str = "assets/cactus.png";
+const HIT_TEXTURE: &str = "assets/cactus_hit.png";
+const VENT_ROCK_COLOR: Color = Color::new(0.22, 0.18, 0.17, 1.0);
+const LAVA_COLOR: Color = Color::new(1.0, 0.45, 0.05, 1.0);
+
+/// Looks a cactus can take on in different biomes; they all sting the same way
+#[derive(Clone, Copy, PartialEq)]
+pub enum CactusKind {
+    Cactus,
+    // Smouldering cone of rock, in the volcano
+    LavaVent,
+}
+
+impl CactusKind {
+    pub const fn id(self) -> &'static str {
+        match self {
+            CactusKind::Cactus => "cactus",
+            CactusKind::LavaVent => "lava_vent",
+        }
+    }
+
+    pub fn obstacle_type(self) -> ObstacleType {
+        let spawn: fn(f32, &ObstacleAssets) -> Box<dyn Obstacle> = match self {
+            CactusKind::Cactus => |x, assets| Box::new(Cactus::new(x, CactusKind::Cactus, assets)),
+            CactusKind::LavaVent => |x, assets| Box::new(Cactus::new(x, CactusKind::LavaVent, assets)),
+        };
+        // The kind is already in the saved id, so it isn't stored in the state again
+        let restore: fn(&SavedObstacle, &ObstacleAssets) -> Option<Box<dyn Obstacle>> = match self {
+            CactusKind::Cactus => |saved, assets| Some(Box::new(Cactus::restore(saved.decode()?, CactusKind::Cactus, assets))),
+            CactusKind::LavaVent => |saved, assets| Some(Box::new(Cactus::restore(saved.decode()?, CactusKind::LavaVent, assets))),
+        };
+        ObstacleType {
+            id: self.id(),
+            assets: &[TEXTURE, HIT_TEXTURE],
+            spawn_weight: 3,
+            traits: ObstacleTraits {
+                stompable: true,
+                small: true,
+                hit_burst: |_| Some(Burst::Sparks),
+                ..ObstacleTraits::default()
+            },
+            spawn,
+            restore,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CactusState {
+    pub x_percent: f32,
+    pub is_hit: bool,
+    pub hit_frame: usize,
+    pub hit_timer: f32,
+}
+
 pub struct Cactus {
+    kind: CactusKind,
     x_percent: f32,
     size_percent: f32,
     texture: Texture2D,
@@ -18,28 +77,32 @@ pub struct Cactus {
 }
 
 impl Cactus {
-    pub async fn load_textures() -> (Texture2D, Texture2D) {
-        let texture = load_texture("assets/cactus.png").await.unwrap();
-        texture.set_filter(FilterMode::Nearest);
-
-        let hit_texture = load_texture("assets/cactus_hit.png").await.unwrap();
-        hit_texture.set_filter(FilterMode::Nearest);
-
-        (texture, hit_texture)
-    }
-
-    pub fn new(x: f32, texture: Texture2D, hit_texture: Texture2D) -> Self {
+    pub fn new(x: f32, kind: CactusKind, assets: &ObstacleAssets) -> Self {
         Self {
+            kind,
             x_percent: x / screen_width(),
             size_percent: 0.15,
-            texture,
-            hit_texture,
+            texture: assets.texture(TEXTURE),
+            hit_texture: assets.texture(HIT_TEXTURE),
             is_hit: false,
             hit_frame: 0,
             hit_timer: 0.0,
         }
     }
 
+    pub fn restore(state: CactusState, kind: CactusKind, assets: &ObstacleAssets) -> Self {
+        Self {
+            kind,
+            x_percent: state.x_percent,
+            size_percent: 0.15,
+            texture: assets.texture(TEXTURE),
+            hit_texture: assets.texture(HIT_TEXTURE),
+            is_hit: state.is_hit,
+            hit_frame: state.hit_frame,
+            hit_timer: state.hit_timer,
+        }
+    }
+
     fn x(&self) -> f32 {
         screen_width() * self.x_percent
     }
@@ -56,6 +119,24 @@ impl Cactus {
     fn height(&self) -> f32 {
         screen_height() * self.size_percent
     }
+
+    // Lava vent: a rock cone with glowing lava at the top, sinking into the ground once hit
+    fn draw_vent(&self) {
+        let sink = if self.is_hit { self.hit_frame as f32 / FRAME_COUNT as f32 } else { 0.0 };
+        let (x, width) = (self.x(), self.width());
+        let bottom = self.y() + self.height();
+        let top = bottom - self.height() * 0.85 * (1.0 - sink);
+        let center = x + width / 2.0;
+        let crater = width * 0.15;
+
+        draw_triangle(Vec2::new(x, bottom), Vec2::new(center - crater, top), Vec2::new(center + crater, top), VENT_ROCK_COLOR);
+        draw_triangle(Vec2::new(x, bottom), Vec2::new(center + crater, top), Vec2::new(x + width, bottom), VENT_ROCK_COLOR);
+
+        let glow = 0.6 + 0.4 * (get_time() as f32 * 4.0 + self.x_percent * 10.0).sin();
+        draw_circle(center, top, crater * 1.6, Color { a: 0.35 * glow, ..LAVA_COLOR });
+        draw_circle(center, top, crater, Color { a: glow, ..LAVA_COLOR });
+        draw_line(center, top, center - crater * 0.5, top + self.height() * 0.25, crater * 0.4, LAVA_COLOR);
+    }
 }
 
 impl Obstacle for Cactus {
@@ -74,7 +155,9 @@ impl Obstacle for Cactus {
     }
 
     fn draw(&self) {
-        if self.is_hit {
+        if self.kind == CactusKind::LavaVent {
+            self.draw_vent();
+        } else if self.is_hit {
             // Draw hit animation from sprite sheet
             let frame_col = self.hit_frame % SPRITE_COLS;
             let frame_row = self.hit_frame / SPRITE_COLS;
@@ -128,10 +211,23 @@ impl Obstacle for Cactus {
         self.x() + self.width() < 0.0
     }
 
+    fn footprint(&self) -> Option<(f32, f32)> {
+        Some((self.x(), self.x() + self.width()))
+    }
+
     fn get_collision_effect(&self, _dino: &crate::models::Dino) -> Effect {
         Effect::Damage(0.15)
     }
 
+    fn snapshot(&self) -> SavedObstacle {
+        SavedObstacle::new(self.kind.id(), &CactusState {
+            x_percent: self.x_percent,
+            is_hit: self.is_hit,
+            hit_frame: self.hit_frame,
+            hit_timer: self.hit_timer,
+        })
+    }
+
     fn on_hit(&mut self) {
         self.is_hit = true;
         self.hit_timer = 0.0;
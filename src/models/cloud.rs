@@ -1,9 +1,20 @@
-use macroquad::prelude::*;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
 
 // Sprite sheet is 2x2 grid
 const SPRITE_COLS: usize = 2;
 const SPRITE_ROWS: usize = 2;
 
+/// Texture-free state of a cloud
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct CloudState {
+    pub x_percent: f32,
+    pub y_percent: f32,
+    pub speed_percent: f32,
+    pub size_percent: f32,
+    pub sprite_index: usize,
+}
+
 pub struct Cloud {
     // Position as percentage
     pub x_percent: f32,
@@ -36,6 +47,27 @@ impl Cloud {
         }
     }
 
+    pub fn restore(state: CloudState, texture: Texture2D) -> Self {
+        Self {
+            x_percent: state.x_percent,
+            y_percent: state.y_percent,
+            speed_percent: state.speed_percent,
+            size_percent: state.size_percent,
+            sprite_index: state.sprite_index,
+            texture,
+        }
+    }
+
+    pub fn snapshot(&self) -> CloudState {
+        CloudState {
+            x_percent: self.x_percent,
+            y_percent: self.y_percent,
+            speed_percent: self.speed_percent,
+            size_percent: self.size_percent,
+            sprite_index: self.sprite_index,
+        }
+    }
+
     fn x(&self) -> f32 {
         screen_width() * self.x_percent
     }
@@ -50,7 +82,8 @@ impl Cloud {
         screen_height() * self.size_percent
     }
 
-    pub fn update(&mut self, dt: f32) {
+    /// Drift left; clouds coming back on screen pick a sprite from the given range
+    pub fn update(&mut self, dt: f32, sprites: (usize, usize)) {
         self.x_percent -= self.speed_percent * dt;
 
         // Reset when off screen (left side)
@@ -58,11 +91,11 @@ impl Cloud {
             self.x_percent = 1.0 + rand::gen_range(0.0, 0.2);
             self.y_percent = rand::gen_range(0.05, 0.3);
             self.size_percent = rand::gen_range(0.3, 0.4);
-            self.sprite_index = rand::gen_range(0, 4);
+            self.sprite_index = rand::gen_range(sprites.0, sprites.1);
         }
     }
 
-    pub fn draw(&self) {
+    pub fn draw(&self, tint: Color) {
         // Calculate source rectangle from sprite sheet
         let frame_width = self.texture.width() / SPRITE_COLS as f32;
         let frame_height = self.texture.height() / SPRITE_ROWS as f32;
@@ -80,7 +113,7 @@ impl Cloud {
             &self.texture,
             self.x(),
             self.y(),
-            WHITE,
+            tint,
             DrawTextureParams {
                 source: Some(source),
                 dest_size: Some(Vec2::new(self.size() * 2.0, self.size() * 2.0)),
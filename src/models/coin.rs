@@ -0,0 +1,78 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Collectible currency that scrolls with the world
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Coin {
+    x_percent: f32,
+    y_percent: f32,
+    size_percent: f32,
+    spin_timer: f32,
+    collected: bool,
+}
+
+impl Coin {
+    pub fn new(x: f32, y_percent: f32) -> Self {
+        Self {
+            x_percent: x / screen_width(),
+            y_percent,
+            size_percent: 0.035,
+            spin_timer: 0.0,
+            collected: false,
+        }
+    }
+
+    /// A short horizontal line of coins at a random height
+    pub fn spawn_row(x: f32) -> Vec<Coin> {
+        let count = rand::gen_range(3, 6);
+        let y_percent = [0.78, 0.65, 0.5][rand::gen_range(0, 3)];
+        let spacing = screen_width() * 0.04;
+        (0..count)
+            .map(|i| Coin::new(x + i as f32 * spacing, y_percent))
+            .collect()
+    }
+
+    fn x(&self) -> f32 {
+        screen_width() * self.x_percent
+    }
+
+    fn y(&self) -> f32 {
+        screen_height() * self.y_percent
+    }
+
+    fn size(&self) -> f32 {
+        screen_height() * self.size_percent
+    }
+
+    pub fn update(&mut self, speed: f32, dt: f32) {
+        let speed_percent = speed / screen_width();
+        self.x_percent -= speed_percent * dt;
+        self.spin_timer += dt;
+    }
+
+    pub fn draw(&self) {
+        // Squash horizontally to fake a spinning coin
+        let radius = self.size() / 2.0;
+        let squash = (self.spin_timer * 5.0).cos().abs().max(0.2);
+        let center_x = self.x() + radius;
+        let center_y = self.y() + radius;
+        draw_ellipse(center_x, center_y, radius * squash, radius, 0.0, GOLD);
+        draw_ellipse_lines(center_x, center_y, radius * squash, radius, 0.0, 2.0, ORANGE);
+    }
+
+    pub fn get_hitbox(&self) -> Rect {
+        Rect::new(self.x(), self.y(), self.size(), self.size())
+    }
+
+    pub fn is_off_screen(&self) -> bool {
+        self.x() + self.size() < 0.0
+    }
+
+    pub fn is_collected(&self) -> bool {
+        self.collected
+    }
+
+    pub fn collect(&mut self) {
+        self.collected = true;
+    }
+}
@@ -0,0 +1,126 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Length of a full dawn-to-dawn cycle, in seconds of play
+const CYCLE_SECONDS: f32 = 150.0;
+// Runs start in the morning
+const START_TIME: f32 = 0.12;
+const STAR_COUNT: u32 = 60;
+// How far ahead of the dino you can see at full night, as percent of screen width
+const NIGHT_VISIBILITY: f32 = 0.45;
+// Width of the fade into darkness beyond the visible range, as percent of screen width
+const VISIBILITY_FADE: f32 = 0.15;
+const NIGHT_COLOR: Color = Color::new(0.02, 0.03, 0.12, 1.0);
+
+// (time of day, sky colour, darkness) keyframes; time runs 0..1 from dawn to the next dawn
+const KEYFRAMES: [(f32, Color, f32); 7] = [
+    (0.0, Color::new(0.98, 0.7, 0.55, 1.0), 0.3),
+    (0.1, SKYBLUE, 0.0),
+    (0.45, SKYBLUE, 0.0),
+    (0.55, Color::new(0.95, 0.5, 0.35, 1.0), 0.35),
+    (0.65, Color::new(0.05, 0.07, 0.2, 1.0), 1.0),
+    (0.9, Color::new(0.05, 0.07, 0.2, 1.0), 1.0),
+    (1.0, Color::new(0.98, 0.7, 0.55, 1.0), 0.3),
+];
+
+/// Time of day: drives the sky colour, stars and moon, and how dark the world gets
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DayCycle {
+    // 0..1, see KEYFRAMES
+    time: f32,
+}
+
+impl DayCycle {
+    pub fn new() -> Self {
+        Self { time: START_TIME }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.time = (self.time + dt / CYCLE_SECONDS).fract();
+    }
+
+    // Sky colour and darkness at the current time, blended between keyframes
+    fn sample(&self) -> (Color, f32) {
+        let next = KEYFRAMES.iter().position(|k| k.0 > self.time).unwrap_or(KEYFRAMES.len() - 1);
+        let (from_time, from_color, from_dark) = KEYFRAMES[next - 1];
+        let (to_time, to_color, to_dark) = KEYFRAMES[next];
+        let t = ((self.time - from_time) / (to_time - from_time)).clamp(0.0, 1.0);
+        let color = Color::new(
+            from_color.r + (to_color.r - from_color.r) * t,
+            from_color.g + (to_color.g - from_color.g) * t,
+            from_color.b + (to_color.b - from_color.b) * t,
+            1.0,
+        );
+        (color, from_dark + (to_dark - from_dark) * t)
+    }
+
+    pub fn sky_color(&self) -> Color {
+        self.sample().0
+    }
+
+    /// 0 in full daylight, 1 in the middle of the night
+    pub fn darkness(&self) -> f32 {
+        self.sample().1
+    }
+
+    /// Clouds pick up the sky's colour and go grey at night
+    pub fn cloud_tint(&self) -> Color {
+        let sky = self.sky_color();
+        let light = 1.0 - self.darkness() * 0.6;
+        Color::new((0.7 + sky.r * 0.3) * light, (0.7 + sky.g * 0.3) * light, (0.7 + sky.b * 0.3) * light, 1.0)
+    }
+
+    /// Stars and the moon, fading in with the dark
+    pub fn draw_sky(&self) {
+        let darkness = self.darkness();
+        if darkness <= 0.0 {
+            return;
+        }
+
+        for i in 0..STAR_COUNT {
+            // Fixed spots from a cheap hash of the star index
+            let hash = i.wrapping_mul(2_654_435_761);
+            let x = (hash % 1000) as f32 / 1000.0 * screen_width();
+            let y = (hash / 1000 % 500) as f32 / 1000.0 * screen_height();
+            let twinkle = 0.6 + 0.4 * (get_time() as f32 * 2.0 + i as f32).sin();
+            let size = screen_height() * if i % 7 == 0 { 0.004 } else { 0.0025 };
+            draw_circle(x, y, size, Color::new(1.0, 1.0, 0.9, darkness * twinkle));
+        }
+
+        // Moon arcs across the sky from dusk to dawn
+        let night_progress = ((self.time - 0.55) / 0.45).clamp(0.0, 1.0);
+        let x = screen_width() * (1.0 - night_progress);
+        let y = screen_height() * (0.35 - 0.25 * (night_progress * std::f32::consts::PI).sin());
+        let radius = screen_height() * 0.045;
+        draw_circle(x, y, radius, Color::new(0.95, 0.95, 0.85, darkness));
+        draw_circle(x + radius * 0.35, y - radius * 0.2, radius * 0.85, Color { a: darkness, ..self.sky_color() });
+    }
+
+    /// Darken the world at night, and black out what's beyond the dino's sight
+    pub fn draw_darkness(&self, dino_x: f32) {
+        let darkness = self.darkness();
+        if darkness <= 0.0 {
+            return;
+        }
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color { a: 0.45 * darkness, ..NIGHT_COLOR });
+
+        // Visibility shrinks as night falls; beyond it things fade to black
+        let visible_until = dino_x + screen_width() * (NIGHT_VISIBILITY + 1.0 - darkness);
+        draw_fade_beyond(visible_until, screen_width() * VISIBILITY_FADE, NIGHT_COLOR, 0.85 * darkness);
+    }
+}
+
+/// Full-height overlay that is clear up to x, ramps up to max_alpha over fade_width,
+/// and stays at max_alpha to the right edge of the screen
+pub fn draw_fade_beyond(x: f32, fade_width: f32, color: Color, max_alpha: f32) {
+    let steps = 12;
+    for step in 0..steps {
+        let step_x = x + fade_width * step as f32 / steps as f32;
+        let alpha = max_alpha * (step + 1) as f32 / steps as f32;
+        draw_rectangle(step_x, 0.0, fade_width / steps as f32, screen_height(), Color { a: alpha, ..color });
+    }
+    let beyond = x + fade_width;
+    if beyond < screen_width() {
+        draw_rectangle(beyond, 0.0, screen_width() - beyond, screen_height(), Color { a: max_alpha, ..color });
+    }
+}
@@ -0,0 +1,165 @@
+use crate::prelude::*;
+use super::Dino;
+
+// How long the landing shockwave ring stays on screen, in seconds
+const SHOCKWAVE_TIME: f32 = 0.3;
+const DASH_COLOR: Color = Color::new(0.6, 0.85, 1.0, 0.6);
+const SLAM_COLOR: Color = Color::new(1.0, 0.6, 0.2, 0.8);
+
+/// Mana abilities on top of double jump and flight
+#[derive(Clone, Copy, PartialEq)]
+pub enum Ability {
+    // Short burst forwards, untouchable while it lasts
+    Dash,
+    // Drop straight down from the air and smash what's nearby on landing
+    GroundSlam,
+}
+
+impl Ability {
+    const ALL: [Ability; 2] = [Ability::Dash, Ability::GroundSlam];
+
+    // Label and key hint shown in the HUD
+    fn icon(self) -> (&'static str, &'static str) {
+        match self {
+            Ability::Dash => ("DASH", "SHIFT"),
+            Ability::GroundSlam => ("SLAM", "X"),
+        }
+    }
+}
+
+impl Dino {
+    pub fn dash(&mut self) {
+        if !self.can_use(Ability::Dash) {
+            return;
+        }
+        self.mana -= self.tuning.dash_mana_cost;
+        self.dash_timer = self.tuning.dash_time;
+        self.dash_cooldown = self.tuning.dash_cooldown;
+        self.knockback_velocity = 0.0;
+    }
+
+    pub fn ground_slam(&mut self) {
+        if !self.can_use(Ability::GroundSlam) || !self.is_jumping || self.is_slamming {
+            return;
+        }
+        self.mana -= self.tuning.slam_mana_cost;
+        self.slam_cooldown = self.tuning.slam_cooldown;
+        self.is_slamming = true;
+        self.is_flying = false;
+        self.velocity_y = screen_height() * self.tuning.slam_speed;
+    }
+
+    fn can_use(&self, ability: Ability) -> bool {
+        !self.is_dead() && !self.is_stunned() && self.cooldown(ability) <= 0.0 && self.mana >= self.mana_cost(ability)
+    }
+
+    fn cooldown(&self, ability: Ability) -> f32 {
+        match ability {
+            Ability::Dash => self.dash_cooldown,
+            Ability::GroundSlam => self.slam_cooldown,
+        }
+    }
+
+    fn max_cooldown(&self, ability: Ability) -> f32 {
+        match ability {
+            Ability::Dash => self.tuning.dash_cooldown,
+            Ability::GroundSlam => self.tuning.slam_cooldown,
+        }
+    }
+
+    fn mana_cost(&self, ability: Ability) -> f32 {
+        match ability {
+            Ability::Dash => self.tuning.dash_mana_cost,
+            Ability::GroundSlam => self.tuning.slam_mana_cost,
+        }
+    }
+
+    pub fn is_dashing(&self) -> bool {
+        self.dash_timer > 0.0
+    }
+
+    /// True once, on the frame a ground slam hits the ground
+    pub fn take_slam_impact(&mut self) -> bool {
+        std::mem::take(&mut self.slam_impact)
+    }
+
+    /// Area a landed ground slam clears, in pixels
+    pub fn slam_area(&self) -> Rect {
+        let reach = screen_width() * self.tuning.slam_radius;
+        let center = self.x() + self.width() / 2.0;
+        Rect::new(center - reach, self.y(), reach * 2.0, self.height())
+    }
+
+    /// Called when the dino touches down
+    pub(crate) fn land_slam(&mut self) {
+        if self.is_slamming {
+            self.is_slamming = false;
+            self.slam_impact = true;
+            self.shockwave_timer = SHOCKWAVE_TIME;
+        }
+    }
+
+    /// Tick cooldowns and move the dino forward while dashing
+    pub(crate) fn update_abilities(&mut self, dt: f32) {
+        self.dash_cooldown = (self.dash_cooldown - dt).max(0.0);
+        self.slam_cooldown = (self.slam_cooldown - dt).max(0.0);
+        self.shockwave_timer = (self.shockwave_timer - dt).max(0.0);
+
+        if self.is_dashing() {
+            self.dash_timer = (self.dash_timer - dt).max(0.0);
+            self.x_percent += self.tuning.dash_speed * dt;
+        }
+    }
+
+    pub(crate) fn reset_abilities(&mut self) {
+        self.dash_timer = 0.0;
+        self.dash_cooldown = 0.0;
+        self.is_slamming = false;
+        self.slam_cooldown = 0.0;
+        self.slam_impact = false;
+        self.shockwave_timer = 0.0;
+    }
+
+    /// Streaks behind a dash, above a slam, and the landing shockwave
+    pub(crate) fn draw_abilities(&self) {
+        let center_y = self.y() + self.height() / 2.0;
+        if self.is_dashing() {
+            for i in 0..4 {
+                let y = center_y + (i as f32 - 1.5) * self.height() * 0.15;
+                let length = self.width() * (0.8 - i as f32 % 2.0 * 0.3);
+                draw_line(self.x() - length, y, self.x() + self.width() * 0.2, y, 3.0, DASH_COLOR);
+            }
+        }
+        if self.is_slamming {
+            for i in 0..3 {
+                let x = self.x() + self.width() * (0.3 + i as f32 * 0.2);
+                draw_line(x, self.y() - self.height() * 0.6, x, self.y() + self.height() * 0.2, 3.0, SLAM_COLOR);
+            }
+        }
+        if self.shockwave_timer > 0.0 {
+            let progress = 1.0 - self.shockwave_timer / SHOCKWAVE_TIME;
+            let radius = screen_width() * self.tuning.slam_radius * progress;
+            let color = Color { a: SLAM_COLOR.a * (1.0 - progress), ..SLAM_COLOR };
+            let feet_y = self.y() + self.height();
+            draw_ellipse_lines(self.x() + self.width() / 2.0, feet_y, radius, radius * 0.25, 0.0, 3.0, color);
+        }
+    }
+
+    /// Ability boxes that fill up as their cooldowns run out; grey while short on mana
+    pub(crate) fn draw_abilities_hud(&self, x: f32, y: f32, size: f32) {
+        for (i, ability) in Ability::ALL.into_iter().enumerate() {
+            let box_width = size * 3.0;
+            let box_x = x + i as f32 * (box_width + size * 0.5);
+            let (label, key) = ability.icon();
+            let ready = 1.0 - (self.cooldown(ability) / self.max_cooldown(ability)).clamp(0.0, 1.0);
+            let color = if self.mana >= self.mana_cost(ability) { SKYBLUE } else { GRAY };
+
+            draw_rectangle(box_x, y, box_width, size, LIGHTGRAY);
+            draw_rectangle(box_x, y, box_width * ready, size, color);
+            draw_rectangle_lines(box_x, y, box_width, size, 2.0, DARKGRAY);
+            draw_text(label, box_x + size * 0.2, y + size * 0.75, size * 0.8, DARKGRAY);
+            let key_width = measure_text(key, None, (size * 0.5) as u16, 1.0).width;
+            draw_text(key, box_x + box_width - key_width - size * 0.1, y + size * 0.9, size * 0.5, DARKGRAY);
+        }
+    }
+}
@@ -1,7 +1,7 @@
-use macroquad::prelude::*;
+use crate::prelude::*;
 use crate::models::Dino;
 use super::main::{SPRITE_COLS, SPRITE_ROWS};
-use super::DeathCause;
+use super::{DeathCause, Status};
 
 impl Dino {
     pub fn draw(&self) {
@@ -9,6 +9,10 @@ impl Dino {
             self._death();
             return;
         }
+        self.draw_abilities();
+        if self.is_blinked_out() {
+            return;
+        }
         if self.is_eating {
             self._eat();
             return;
@@ -24,6 +28,15 @@ impl Dino {
         self._run();
     }
 
+    // Skip every other blink interval while invulnerable
+    fn is_blinked_out(&self) -> bool {
+        self.statuses
+            .iter()
+            .find(|s| s.status == Status::Invulnerable)
+            .map(|s| (s.remaining / self.tuning.invulnerability_blink_interval) as u32 % 2 == 1)
+            .unwrap_or(false)
+    }
+
     pub fn draw_stand(&self) {
         let frame_col = self.current_frame % SPRITE_COLS;
         let frame_row = self.current_frame / SPRITE_COLS;
@@ -43,7 +56,7 @@ impl Dino {
             &self.stand_texture,
             self.x(),
             self.y(),
-            WHITE,
+            self.tuning.tint,
             DrawTextureParams {
                 source: Some(source_rect),
                 dest_size: Some(Vec2::new(self.width(), self.height())),
@@ -58,7 +71,7 @@ impl Dino {
             &self.flight_texture,
             self.x() - self.width() * scale / 4.0,
             self.y() - self.height() * scale / 5.0,
-            WHITE,
+            self.tuning.tint,
             DrawTextureParams {
                 dest_size: Some(Vec2::new(self.width() * scale, self.height() * scale)),
                 ..Default::default()
@@ -68,7 +81,7 @@ impl Dino {
 
     fn _death(&self) {
         let texture = match self.death_cause {
-            Some(DeathCause::Impact) => &self.death_impact_texture,
+            Some(DeathCause::Impact) | Some(DeathCause::Fell) => &self.death_impact_texture,
             Some(DeathCause::NoHP) => &self.death_no_hp_texture,
             None => &self.death_no_hp_texture, // Default fallback
         };
@@ -91,7 +104,7 @@ impl Dino {
             texture,
             self.x(),
             self.y(),
-            WHITE,
+            self.tuning.tint,
             DrawTextureParams {
                 source: Some(source_rect),
                 dest_size: Some(Vec2::new(self.width(), self.height())),
@@ -119,7 +132,7 @@ impl Dino {
             &self.eating_texture,
             self.x(),
             self.y(),
-            WHITE,
+            self.tuning.tint,
             DrawTextureParams {
                 source: Some(source_rect),
                 dest_size: Some(Vec2::new(self.width(), self.height())),
@@ -147,7 +160,7 @@ impl Dino {
             if self.is_ducking { &self.duck_texture } else { &self.run_texture },
             self.x(),
             self.y(),
-            WHITE,
+            self.tuning.tint,
             DrawTextureParams {
                 source: Some(source_rect),
                 dest_size: Some(Vec2::new(self.width(), self.height())),
@@ -181,7 +194,7 @@ impl Dino {
             &self.jump_texture,
             self.x(),
             self.y(),
-            WHITE,
+            self.tuning.tint,
             DrawTextureParams {
                 source: Some(source_rect),
                 dest_size: Some(Vec2::new(self.width(), self.height())),
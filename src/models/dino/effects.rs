@@ -1,25 +1,36 @@
 use super::{Dino, DeathCause};
+use super::status::Status;
 
 /// Effects that can be applied to the Dino
 pub enum Effect {
     Damage(f32),
     Kill,
     Eaten,
+    RestoreMana(f32),
+    // Fill mana all the way up to the dino's maximum
+    RefillMana,
+    // Timed status with its duration in seconds
+    Status(Status, f32),
+    // Several effects from a single collision
+    Combined(Vec<Effect>),
 }
 
 impl Dino {
     pub fn apply_effect(&mut self, effect: Effect) {
         match effect {
             Effect::Damage(amount) => {
-                self.health = (self.health - amount).max(0.0);
-                if self.is_dead() && self.death_cause.is_none() {
-                    self.death_cause = Some(DeathCause::NoHP);
-                    self.current_frame = 0;
-                    self.animation_timer = 0.0;
-                    self.is_ducking = false;
+                if self.is_invulnerable() {
+                    return;
+                }
+                self.take_damage(amount);
+                if !self.is_dead() {
+                    self.on_damaged();
                 }
             }
             Effect::Kill => {
+                if self.is_invulnerable() || self.consume_shield() {
+                    return;
+                }
                 self.health = 0.0;
                 self.mana = 0.0;
                 if self.death_cause.is_none() {
@@ -31,13 +42,66 @@ impl Dino {
             }
             Effect::Eaten => {
                 self.health = (self.health + 0.2).min(1.0);
-                self.mana = (self.mana + 0.3).min(1.0);
+                self.mana = (self.mana + 0.3).min(self.tuning.max_mana);
                 self.has_eaten = true;
                 if self.is_eating && self.current_frame < 2 {
                     self.current_frame = 2;
                     self.animation_timer = 0.0;
                 }
             }
+            Effect::RestoreMana(amount) => {
+                self.mana = (self.mana + amount).min(self.tuning.max_mana);
+            }
+            Effect::RefillMana => {
+                self.mana = self.tuning.max_mana;
+            }
+            Effect::Status(status, duration) => {
+                // Harmful statuses are blocked by invulnerability like any other hit
+                if self.is_invulnerable() && status.is_harmful() {
+                    return;
+                }
+                self.add_status(status, duration);
+            }
+            Effect::Combined(effects) => {
+                // Statuses first, so the damage's invulnerability doesn't block them
+                let (statuses, rest): (Vec<_>, Vec<_>) =
+                    effects.into_iter().partition(|e| matches!(e, Effect::Status(..)));
+                for effect in statuses.into_iter().chain(rest) {
+                    self.apply_effect(effect);
+                }
+            }
+        }
+    }
+
+    /// Invulnerability, knockback and a short stagger after surviving a hit
+    fn on_damaged(&mut self) {
+        self.add_status(Status::Invulnerable, self.tuning.hit_invulnerability_time);
+        self.add_status(Status::Stun, self.tuning.stagger_time);
+        self.knockback_velocity = -self.tuning.knockback_speed;
+    }
+
+    /// Fell off the bottom of the screen into a pit; nothing saves the dino from this
+    pub(crate) fn fall_to_death(&mut self) {
+        self.health = 0.0;
+        self.mana = 0.0;
+        self.velocity_y = 0.0;
+        self.is_jumping = false;
+        self.is_flying = false;
+        if self.death_cause.is_none() {
+            self.death_cause = Some(DeathCause::Fell);
+            self.current_frame = 0;
+            self.animation_timer = 0.0;
+        }
+    }
+
+    /// Subtract health, starting the no-HP death if it runs out
+    pub(crate) fn take_damage(&mut self, amount: f32) {
+        self.health = (self.health - amount).max(0.0);
+        if self.is_dead() && self.death_cause.is_none() {
+            self.death_cause = Some(DeathCause::NoHP);
+            self.current_frame = 0;
+            self.animation_timer = 0.0;
+            self.is_ducking = false;
         }
     }
 
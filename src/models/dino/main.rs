@@ -1,9 +1,11 @@
-use macroquad::prelude::*;
+use crate::prelude::*;
 use crate::models::Dino;
+use crate::models::terrain::BASE_HEIGHT;
+use super::DinoTuning;
 
-// Ground at 85% of screen height
+// Flat ground level, in pixels
 pub fn ground_y() -> f32 {
-    screen_height() * 0.85
+    screen_height() * BASE_HEIGHT
 }
 
 // Physics scaled to screen height
@@ -17,14 +19,67 @@ pub(crate) const SPRITE_ROWS: usize = 2;
 pub(crate) const FRAME_COUNT: usize = 4;
 const ANIMATION_SPEED: f32 = 0.25;
 const STAND_ANIMATION_SPEED: f32 = 0.5;
-const EATING_ANIMATION_SPEED: f32 = 0.1;
-const DOUBLE_JUMP_MANA_COST: f32 = 0.05;
+// Horizontal spot the dino runs at, as percent of screen width
+pub(crate) const HOME_X_PERCENT: f32 = 0.15;
+// Furthest forward wind, dashes or slipping can carry the dino, as percent of screen width
+const MAX_X_PERCENT: f32 = 0.45;
+// Biggest drop (percent of screen height) the dino follows while running instead of falling
+const MAX_STEP_DOWN: f32 = 0.02;
+// How far below a surface the feet may have been last frame and still land on it (percent of
+// screen height); covers ground rising under a falling dino on a slope
+const LANDING_TOLERANCE: f32 = 0.01;
+
+/// Whether a falling dino touches down this frame. It has to come onto the surface from above:
+/// a dino that dropped below the lip of a pit keeps falling instead of snapping back up.
+pub(crate) fn lands(previous_y: f32, y: f32, velocity_y: f32, ground_level: f32) -> bool {
+    velocity_y > 0.0 && y >= ground_level && previous_y <= ground_level + LANDING_TOLERANCE
+}
+
+/// How a step of vertical motion left the dino
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum Footing {
+    Airborne,
+    // Touched down this step
+    Landed,
+    Running,
+    // Ran off a ledge or into a pit this step
+    WalkedOff,
+}
+
+/// One step of vertical motion. `y` and `ground_level` (where the top of the dino sits when
+/// standing) are in percent of screen height, `velocity_y` in pixels per second.
+/// In the air it falls under gravity (unless something holds it up, like flying) and lands only
+/// when coming onto the ground from above; running, it follows gentle slopes and drops off
+/// anything steeper.
+pub(crate) fn step_vertical(y: &mut f32, velocity_y: &mut f32, airborne: bool, falls: bool, ground_level: f32, dt: f32) -> Footing {
+    if !airborne {
+        if ground_level - *y > MAX_STEP_DOWN {
+            *velocity_y = 0.0;
+            return Footing::WalkedOff;
+        }
+        *y = ground_level;
+        return Footing::Running;
+    }
+
+    let previous_y = *y;
+    if falls {
+        *velocity_y += gravity() * dt;
+        *y += (*velocity_y * dt) / screen_height();
+    }
+    if lands(previous_y, *y, *velocity_y, ground_level) {
+        *y = ground_level;
+        *velocity_y = 0.0;
+        Footing::Landed
+    } else {
+        Footing::Airborne
+    }
+}
 
 
 impl Dino {
     pub fn reset(&mut self) {
-        let ground = 0.85;
-        self.x_percent = 0.15;
+        let ground = BASE_HEIGHT;
+        self.x_percent = HOME_X_PERCENT;
         self.y_percent = ground - self.size_percent;
         self.velocity_y = 0.0;
         self.is_jumping = false;
@@ -35,10 +90,21 @@ impl Dino {
         self.current_frame = 0;
         self.animation_timer = 0.0;
         self.health = 1.0;
-        self.mana = 1.0;
+        self.mana = self.tuning.max_mana;
         self.death_cause = None;
         self.can_double_jump = false;
         self.is_standing = false;
+        self.clear_statuses();
+        self.knockback_velocity = 0.0;
+        self.just_landed = false;
+        self.reset_abilities();
+        self.ground_percent = Some(ground);
+    }
+
+    /// Swap in new tuning (e.g. after buying upgrades); takes effect immediately
+    pub fn set_tuning(&mut self, tuning: DinoTuning) {
+        self.tuning = tuning;
+        self.mana = self.mana.min(self.tuning.max_mana);
     }
 
     pub fn x(&self) -> f32 {
@@ -58,25 +124,28 @@ impl Dino {
     }
 
     pub fn jump(&mut self) {
+        if self.is_stunned() {
+            return;
+        }
         if !self.is_jumping {
             self.velocity_y = screen_height() * -1.05;
             self.is_jumping = true;
             self.can_double_jump = true;
             self.current_frame = 0;
             self.animation_timer = 0.0;
-        } else if self.can_double_jump && self.mana >= DOUBLE_JUMP_MANA_COST {
+        } else if self.can_double_jump && self.mana >= self.tuning.double_jump_mana_cost {
             self.velocity_y = screen_height() * -0.8;
-            self.mana -= DOUBLE_JUMP_MANA_COST;
+            self.mana -= self.tuning.double_jump_mana_cost;
             self.can_double_jump = false;
         }
     }
 
     pub fn duck(&mut self, ducking: bool) {
-        self.is_ducking = ducking;
+        self.is_ducking = ducking && !self.is_stunned();
     }
 
     pub fn fly(&mut self, flying: bool, vertical_direction: f32) {
-        if self.is_jumping && flying && self.mana > 0.0 {
+        if self.is_jumping && flying && self.mana > 0.0 && !self.is_stunned() {
             self.is_flying = true;
             self.fly_direction = vertical_direction;
         } else {
@@ -86,7 +155,7 @@ impl Dino {
     }
 
     pub fn eat(&mut self) {
-        if !self.is_eating && !self.is_dead() {
+        if !self.is_eating && !self.is_dead() && !self.is_stunned() {
             self.is_eating = true;
             self.has_eaten = false;
             self.current_frame = 0;
@@ -98,8 +167,45 @@ impl Dino {
         self.is_eating
     }
 
+    /// In the air and on the way down
+    pub fn is_falling(&self) -> bool {
+        self.is_jumping && self.velocity_y > 0.0
+    }
+
+    /// Spring back up after landing on something, e.g. a stomped enemy
+    pub fn bounce(&mut self) {
+        self.velocity_y = screen_height() * -0.9;
+        self.is_jumping = true;
+        self.can_double_jump = true;
+        self.is_slamming = false;
+    }
+
+    /// Whether the dino touched down since the last call
+    pub fn take_landing(&mut self) -> bool {
+        std::mem::take(&mut self.just_landed)
+    }
+
+    /// Left and right edge of the feet, and their y, in pixels; used to query the terrain
+    pub fn feet(&self) -> (f32, f32, f32) {
+        let hitbox = self.get_hitbox();
+        (hitbox.x - hitbox.r, hitbox.x + hitbox.r, self.y() + self.height())
+    }
+
+    /// Ground surface under the dino for this frame, None when over a pit
+    pub fn set_ground(&mut self, ground_percent: Option<f32>) {
+        self.ground_percent = ground_percent;
+    }
+
+    /// Weather for this frame: wet ground, and wind as (horizontal speed in screen widths
+    /// per second, vertical acceleration in screen heights per second squared)
+    pub fn set_weather(&mut self, slippery: bool, wind: Vec2) {
+        self.is_slippery = slippery;
+        self.wind = wind;
+    }
+
     pub fn update(&mut self, dt: f32) {
-        let ground = 0.85;
+        // Nothing to land on over a pit
+        let ground = self.ground_percent.unwrap_or(f32::INFINITY);
         let current_height = if self.is_ducking {
             self.size_percent * 0.5
         } else {
@@ -119,23 +225,26 @@ impl Dino {
 
             // Fall to the ground if dead while jumping
             if self.is_jumping {
-                self.velocity_y += gravity() * dt;
-                self.y_percent += (self.velocity_y * dt) / screen_height();
-
-                // Land when falling and reached ground
-                if self.velocity_y > 0.0 && self.y_percent >= ground_level {
-                    self.y_percent = ground_level;
-                    self.velocity_y = 0.0;
+                if step_vertical(&mut self.y_percent, &mut self.velocity_y, true, true, ground_level, dt) == Footing::Landed {
                     self.is_jumping = false;
                     self.is_flying = false;
                 }
+                // Dropped into a pit: stop once out of sight
+                if self.y_percent > 1.0 {
+                    self.is_jumping = false;
+                    self.velocity_y = 0.0;
+                }
             }
             return;
         }
 
+        self.update_statuses(dt);
+        self.update_abilities(dt);
+        self.update_knockback(dt);
+
         if self.is_flying {
             if self.mana > 0.0 {
-                self.mana -= 0.1 * dt; // 10% per second
+                self.mana -= self.tuning.flight_mana_drain * dt;
                 
                 // Smooth vertical movement
                 let target_velocity = self.fly_direction * screen_height() * 0.6;
@@ -165,7 +274,7 @@ impl Dino {
 
         if self.is_eating {
             self.animation_timer += dt;
-            if self.animation_timer >= EATING_ANIMATION_SPEED {
+            if self.animation_timer >= self.tuning.eat_frame_time {
                 self.animation_timer = 0.0;
                 self.current_frame += 1;
                 
@@ -183,19 +292,32 @@ impl Dino {
         }
 
         if self.is_jumping {
-            if !self.is_flying {
-                self.velocity_y += gravity() * dt;
-                self.y_percent += (self.velocity_y * dt) / screen_height();
-            }
-
-            // Land when falling and reached ground
-            if self.velocity_y > 0.0 && self.y_percent >= ground_level {
-                self.y_percent = ground_level;
-                self.velocity_y = 0.0;
+            self.x_percent = (self.x_percent + self.wind.x * dt).min(MAX_X_PERCENT);
+            self.velocity_y += self.wind.y * screen_height() * dt;
+        }
+        // Runs along the ground at full height, ducking only shortens the hitbox
+        let level = if self.is_jumping { ground_level } else { ground - self.size_percent };
+        match step_vertical(&mut self.y_percent, &mut self.velocity_y, self.is_jumping, !self.is_flying, level, dt) {
+            Footing::Landed => {
                 self.is_jumping = false;
                 self.is_flying = false;
                 self.current_frame = 0;
+                self.just_landed = true;
+                self.land_slam();
+                if self.is_slippery {
+                    self.knockback_velocity = self.tuning.slip_speed;
+                }
             }
+            Footing::WalkedOff => {
+                self.is_jumping = true;
+                self.can_double_jump = true;
+            }
+            Footing::Airborne | Footing::Running => {}
+        }
+
+        if self.y_percent > 1.0 {
+            self.fall_to_death();
+            return;
         }
 
         // Update animation (both running and jumping)
@@ -213,6 +335,22 @@ impl Dino {
         }
     }
 
+    // Slide back from a hit, then walk back to the running spot (from either side, after a dash)
+    fn update_knockback(&mut self, dt: f32) {
+        self.x_percent += self.knockback_velocity * dt;
+        self.knockback_velocity *= (1.0 - self.tuning.knockback_damping * dt).max(0.0);
+        if self.knockback_velocity.abs() < 0.01 && !self.is_dashing() {
+            self.knockback_velocity = 0.0;
+            let recovery = self.tuning.knockback_recovery_speed * dt;
+            self.x_percent = if self.x_percent < HOME_X_PERCENT {
+                (self.x_percent + recovery).min(HOME_X_PERCENT)
+            } else {
+                (self.x_percent - recovery).max(HOME_X_PERCENT)
+            };
+        }
+        self.x_percent = self.x_percent.clamp(0.0, MAX_X_PERCENT);
+    }
+
     pub fn get_hitbox(&self) -> Circle {
         if self.is_jumping {
             Circle::new(
@@ -229,3 +367,55 @@ impl Dino {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Terrain;
+
+    // Run a dino (feet as wide as the real hitbox) into a pit at world speed, stepping it with
+    // the same `step_vertical` as `Dino::update`. Returns the lowest y reached.
+    fn run_into_pit(pit_width: f32) -> f32 {
+        let size = 0.25;
+        let radius = screen_height() * size / 4.0;
+        let center_x = screen_width() * HOME_X_PERCENT + screen_height() * size / 2.0;
+        let speed = screen_width() * 0.2;
+        let dt = 1.0 / 60.0;
+
+        let mut terrain = Terrain::with_pit(0.4, pit_width);
+        let mut y = BASE_HEIGHT - size;
+        let mut velocity_y = 0.0;
+        let mut airborne = false;
+        for _ in 0..300 {
+            terrain.update(speed, dt);
+            let feet_y = (y + size) * screen_height();
+            let ground = terrain.surface_under(center_x - radius, center_x + radius, feet_y).unwrap_or(f32::INFINITY);
+            match step_vertical(&mut y, &mut velocity_y, airborne, true, ground - size, dt) {
+                Footing::WalkedOff => airborne = true,
+                Footing::Landed => airborne = false,
+                Footing::Airborne | Footing::Running => {}
+            }
+            if y > 1.0 {
+                break;
+            }
+        }
+        y
+    }
+
+    #[test]
+    fn walking_into_the_narrowest_pit_falls_to_death() {
+        assert!(run_into_pit(0.06) > 1.0);
+    }
+
+    #[test]
+    fn walking_into_the_widest_pit_falls_to_death() {
+        assert!(run_into_pit(0.1) > 1.0);
+    }
+
+    #[test]
+    fn lands_only_when_coming_from_above() {
+        assert!(lands(0.5, 0.61, 1.0, 0.6));
+        assert!(!lands(0.65, 0.7, 1.0, 0.6));
+        assert!(!lands(0.5, 0.61, -1.0, 0.6));
+    }
+}
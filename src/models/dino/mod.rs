@@ -2,16 +2,26 @@ mod main;
 mod draw;
 mod stats;
 mod effects;
+mod snapshot;
+mod status;
+mod tuning;
+mod abilities;
 
 use macroquad::miniquad::FilterMode;
-use macroquad::prelude::{load_texture, Texture2D};
+use macroquad::prelude::{load_texture, Texture2D, Vec2};
+use serde::{Deserialize, Serialize};
 pub use main::ground_y;
 pub use effects::Effect;
+pub use snapshot::DinoState;
+pub use status::Status;
+use status::ActiveStatus;
+pub use tuning::DinoTuning;
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum DeathCause {
     Impact,
     NoHP,
+    Fell,
 }
 
 pub struct Dino {
@@ -40,16 +50,35 @@ pub struct Dino {
     pub(crate) stand_texture: Texture2D,
     // Stats
     pub(crate) health: f32,  // 0.0 - 1.0
-    pub(crate) mana: f32,    // 0.0 - 1.0
+    pub(crate) mana: f32,    // 0.0 - tuning.max_mana
     pub(crate) death_cause: Option<DeathCause>,
     pub(crate) can_double_jump: bool,
     pub(crate) is_standing: bool,
+    pub(crate) statuses: Vec<ActiveStatus>,
+    // Horizontal push after a hit, as percent of screen width per second
+    pub(crate) knockback_velocity: f32,
+    pub(crate) tuning: DinoTuning,
+    // Ground surface under the dino (percent of screen height), None over a pit
+    pub(crate) ground_percent: Option<f32>,
+    // Dash time left and cooldowns, in seconds
+    pub(crate) dash_timer: f32,
+    pub(crate) dash_cooldown: f32,
+    pub(crate) is_slamming: bool,
+    pub(crate) slam_cooldown: f32,
+    // Set when a slam lands, until the game picks it up
+    pub(crate) slam_impact: bool,
+    pub(crate) shockwave_timer: f32,
+    // Set on touching down, until the game picks it up (for landing dust)
+    pub(crate) just_landed: bool,
+    // Weather for this frame: wet ground makes landings slide, wind pushes the dino in the air
+    pub(crate) is_slippery: bool,
+    pub(crate) wind: Vec2,
 }
 
 impl Dino {
     pub async fn new() -> Self {
         let size_percent = 0.25;
-        let ground = 0.85;
+        let ground = super::terrain::BASE_HEIGHT;
 
         let run_texture = load_texture("assets/dino/run.png").await.unwrap();
         run_texture.set_filter(FilterMode::Nearest);
@@ -76,7 +105,7 @@ impl Dino {
         stand_texture.set_filter(FilterMode::Nearest);
 
         Self {
-            x_percent: 0.15,
+            x_percent: main::HOME_X_PERCENT,
             y_percent: ground - size_percent,
             size_percent,
             velocity_y: 0.0,
@@ -101,6 +130,19 @@ impl Dino {
             death_cause: None,
             can_double_jump: false,
             is_standing: true,
+            statuses: Vec::new(),
+            knockback_velocity: 0.0,
+            tuning: DinoTuning::default(),
+            ground_percent: Some(ground),
+            dash_timer: 0.0,
+            dash_cooldown: 0.0,
+            is_slamming: false,
+            slam_cooldown: 0.0,
+            slam_impact: false,
+            shockwave_timer: 0.0,
+            just_landed: false,
+            is_slippery: false,
+            wind: Vec2::ZERO,
         }
     }
 }
\ No newline at end of file
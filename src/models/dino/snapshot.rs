@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use super::{Dino, DeathCause};
+use super::status::ActiveStatus;
+
+/// Plain-data copy of the dino's simulation state (everything except textures)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DinoState {
+    pub x_percent: f32,
+    pub y_percent: f32,
+    pub velocity_y: f32,
+    pub is_jumping: bool,
+    pub is_flying: bool,
+    pub fly_direction: f32,
+    pub is_ducking: bool,
+    pub is_eating: bool,
+    pub has_eaten: bool,
+    pub current_frame: usize,
+    pub animation_timer: f32,
+    pub health: f32,
+    pub mana: f32,
+    pub death_cause: Option<DeathCause>,
+    pub can_double_jump: bool,
+    pub is_standing: bool,
+    pub statuses: Vec<ActiveStatus>,
+    pub knockback_velocity: f32,
+    pub dash_timer: f32,
+    pub dash_cooldown: f32,
+    pub is_slamming: bool,
+    pub slam_cooldown: f32,
+    pub shockwave_timer: f32,
+}
+
+impl Dino {
+    pub fn snapshot(&self) -> DinoState {
+        DinoState {
+            x_percent: self.x_percent,
+            y_percent: self.y_percent,
+            velocity_y: self.velocity_y,
+            is_jumping: self.is_jumping,
+            is_flying: self.is_flying,
+            fly_direction: self.fly_direction,
+            is_ducking: self.is_ducking,
+            is_eating: self.is_eating,
+            has_eaten: self.has_eaten,
+            current_frame: self.current_frame,
+            animation_timer: self.animation_timer,
+            health: self.health,
+            mana: self.mana,
+            death_cause: self.death_cause,
+            can_double_jump: self.can_double_jump,
+            is_standing: self.is_standing,
+            statuses: self.statuses.clone(),
+            knockback_velocity: self.knockback_velocity,
+            dash_timer: self.dash_timer,
+            dash_cooldown: self.dash_cooldown,
+            is_slamming: self.is_slamming,
+            slam_cooldown: self.slam_cooldown,
+            shockwave_timer: self.shockwave_timer,
+        }
+    }
+
+    pub fn restore(&mut self, state: &DinoState) {
+        self.x_percent = state.x_percent;
+        self.y_percent = state.y_percent;
+        self.velocity_y = state.velocity_y;
+        self.is_jumping = state.is_jumping;
+        self.is_flying = state.is_flying;
+        self.fly_direction = state.fly_direction;
+        self.is_ducking = state.is_ducking;
+        self.is_eating = state.is_eating;
+        self.has_eaten = state.has_eaten;
+        self.current_frame = state.current_frame;
+        self.animation_timer = state.animation_timer;
+        self.health = state.health;
+        self.mana = state.mana;
+        self.death_cause = state.death_cause;
+        self.can_double_jump = state.can_double_jump;
+        self.is_standing = state.is_standing;
+        self.statuses = state.statuses.clone();
+        self.knockback_velocity = state.knockback_velocity;
+        self.dash_timer = state.dash_timer;
+        self.dash_cooldown = state.dash_cooldown;
+        self.is_slamming = state.is_slamming;
+        self.slam_cooldown = state.slam_cooldown;
+        self.slam_impact = false;
+        self.just_landed = false;
+        self.shockwave_timer = state.shockwave_timer;
+    }
+}
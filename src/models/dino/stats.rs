@@ -1,4 +1,4 @@
-use macroquad::prelude::*;
+use crate::prelude::*;
 use super::Dino;
 use super::ground_y;
 
@@ -31,12 +31,27 @@ impl Dino {
             start_y + bar_height + spacing,
             bar_width,
             bar_height,
-            self.mana,
+            self.mana / self.tuning.max_mana,
             BLUE,
             "MP",
         );
+
+        // Active status icons to the right of the bars
+        self.draw_statuses(
+            margin + bar_width + bar_height * 3.0,
+            start_y,
+            bar_height * 1.5,
+        );
+
+        // Ability cooldowns on the mana bar's row
+        self.draw_abilities_hud(
+            margin + bar_width + bar_height * 3.0,
+            start_y + bar_height + spacing,
+            bar_height * 1.5,
+        );
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn draw_bar(
         &self,
         x: f32,
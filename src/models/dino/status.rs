@@ -0,0 +1,210 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::Dino;
+
+// Per-second strength of the over-time statuses (scaled by stacks)
+const POISON_DAMAGE_PER_SECOND: f32 = 0.025;
+const MANA_DRAIN_PER_SECOND: f32 = 0.15;
+const MAX_POISON_STACKS: u32 = 3;
+const MAX_MANA_DRAIN_DURATION: f32 = 4.0;
+// World speed multipliers while slowed or boosted
+const SLOW_MULTIPLIER: f32 = 0.7;
+const SPEED_BOOST_MULTIPLIER: f32 = 1.25;
+// World time scale under the slow-time power-up
+const SLOW_TIME_SCALE: f32 = 0.5;
+const SCORE_MULTIPLIER: u32 = 2;
+// Grace period after a shield soaks up a kill
+const SHIELD_BREAK_INVULNERABILITY: f32 = 1.0;
+
+/// Timed effects that stay on the dino for a while
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Status {
+    Poison,
+    Slow,
+    SpeedBoost,
+    Invulnerable,
+    ManaDrain,
+    Stun,
+    // Power-ups
+    Shield,
+    Magnet,
+    ScoreMultiplier,
+    SlowTime,
+}
+
+/// How a status reacts when it is applied again while still active
+enum Stacking {
+    // Keep the longer of the two durations
+    Refresh,
+    // Add the new duration to what is left, up to a cap
+    Extend(f32),
+    // Add a stack (up to a cap) and refresh the duration
+    Intensity(u32),
+}
+
+impl Status {
+    fn stacking(self) -> Stacking {
+        match self {
+            Status::Poison => Stacking::Intensity(MAX_POISON_STACKS),
+            Status::ManaDrain => Stacking::Extend(MAX_MANA_DRAIN_DURATION),
+            _ => Stacking::Refresh,
+        }
+    }
+
+    /// Harmful statuses are blocked while invulnerable
+    pub fn is_harmful(self) -> bool {
+        matches!(self, Status::Poison | Status::Slow | Status::ManaDrain | Status::Stun)
+    }
+
+    fn icon(self) -> (&'static str, Color) {
+        match self {
+            Status::Poison => ("P", DARKGREEN),
+            Status::Slow => ("S", DARKBLUE),
+            Status::SpeedBoost => ("B", ORANGE),
+            Status::Invulnerable => ("I", GOLD),
+            Status::ManaDrain => ("Dr", PURPLE),
+            Status::Stun => ("Z", DARKGRAY),
+            Status::Shield => ("Sh", SKYBLUE),
+            Status::Magnet => ("M", RED),
+            Status::ScoreMultiplier => ("x2", GOLD),
+            Status::SlowTime => ("T", VIOLET),
+        }
+    }
+}
+
+/// A status currently affecting the dino
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct ActiveStatus {
+    pub status: Status,
+    pub remaining: f32,
+    // Full duration of the latest application, for HUD timers
+    pub duration: f32,
+    pub stacks: u32,
+}
+
+impl Dino {
+    pub(crate) fn add_status(&mut self, status: Status, duration: f32) {
+        let Some(active) = self.statuses.iter_mut().find(|s| s.status == status) else {
+            self.statuses.push(ActiveStatus {
+                status,
+                remaining: duration,
+                duration,
+                stacks: 1,
+            });
+            return;
+        };
+
+        match status.stacking() {
+            Stacking::Refresh => {
+                if duration > active.remaining {
+                    active.remaining = duration;
+                    active.duration = duration;
+                }
+            }
+            Stacking::Extend(cap) => {
+                active.remaining = (active.remaining + duration).min(cap);
+                active.duration = active.duration.max(active.remaining);
+            }
+            Stacking::Intensity(max_stacks) => {
+                active.stacks = (active.stacks + 1).min(max_stacks);
+                active.remaining = duration;
+                active.duration = duration;
+            }
+        }
+    }
+
+    pub fn has_status(&self, status: Status) -> bool {
+        self.statuses.iter().any(|s| s.status == status)
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.has_status(Status::Stun)
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.has_status(Status::Invulnerable) || self.is_dashing()
+    }
+
+    /// Multiplier the world applies to its scroll speed
+    pub fn speed_multiplier(&self) -> f32 {
+        let mut multiplier = 1.0;
+        if self.has_status(Status::Slow) {
+            multiplier *= SLOW_MULTIPLIER;
+        }
+        if self.has_status(Status::SpeedBoost) {
+            multiplier *= SPEED_BOOST_MULTIPLIER;
+        }
+        multiplier
+    }
+
+    /// Scale applied to the world's clock (obstacles, spawning), not the dino's
+    pub fn time_scale(&self) -> f32 {
+        if self.has_status(Status::SlowTime) {
+            SLOW_TIME_SCALE
+        } else {
+            1.0
+        }
+    }
+
+    pub fn score_multiplier(&self) -> u32 {
+        if self.has_status(Status::ScoreMultiplier) {
+            SCORE_MULTIPLIER
+        } else {
+            1
+        }
+    }
+
+    /// Use up the shield, if any; returns whether one was there to absorb the hit
+    pub(crate) fn consume_shield(&mut self) -> bool {
+        if !self.has_status(Status::Shield) {
+            return false;
+        }
+        self.statuses.retain(|s| s.status != Status::Shield);
+        self.add_status(Status::Invulnerable, SHIELD_BREAK_INVULNERABILITY);
+        true
+    }
+
+    /// Tick over-time statuses and drop the expired ones
+    pub(crate) fn update_statuses(&mut self, dt: f32) {
+        let mut poison_damage = 0.0;
+        for active in &mut self.statuses {
+            let tick = dt.min(active.remaining);
+            match active.status {
+                Status::Poison => poison_damage += POISON_DAMAGE_PER_SECOND * active.stacks as f32 * tick,
+                Status::ManaDrain => self.mana = (self.mana - MANA_DRAIN_PER_SECOND * tick).max(0.0),
+                _ => {}
+            }
+            active.remaining -= dt;
+        }
+        self.statuses.retain(|s| s.remaining > 0.0);
+
+        if poison_damage > 0.0 {
+            // Poison ticks are not fresh hits, so they bypass invulnerability
+            self.take_damage(poison_damage);
+        }
+    }
+
+    pub(crate) fn clear_statuses(&mut self) {
+        self.statuses.clear();
+    }
+
+    /// Row of status icons with a shrinking timer underneath each
+    pub(crate) fn draw_statuses(&self, x: f32, y: f32, size: f32) {
+        for (i, active) in self.statuses.iter().enumerate() {
+            let icon_x = x + i as f32 * size * 1.3;
+            let (label, color) = active.status.icon();
+
+            draw_rectangle(icon_x, y, size, size, color);
+            draw_rectangle_lines(icon_x, y, size, size, 2.0, DARKGRAY);
+            let font_size = if label.len() > 1 { size * 0.7 } else { size };
+            let text_width = measure_text(label, None, font_size as u16, 1.0).width;
+            draw_text(label, icon_x + (size - text_width) / 2.0, y + size * 0.75, font_size, WHITE);
+            if active.stacks > 1 {
+                draw_text(&format!("x{}", active.stacks), icon_x + size * 0.6, y, size * 0.6, DARKGRAY);
+            }
+
+            let fraction = (active.remaining / active.duration).clamp(0.0, 1.0);
+            draw_rectangle(icon_x, y + size + 2.0, size * fraction, size * 0.15, color);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use macroquad::prelude::{Color, WHITE};
+
+/// Gameplay values for the dino that designers (and shop upgrades) may tweak
+#[derive(Clone)]
+pub struct DinoTuning {
+    // Size of the mana pool (1.0 = the base bar)
+    pub max_mana: f32,
+    pub double_jump_mana_cost: f32,
+    // Mana spent per second of flight
+    pub flight_mana_drain: f32,
+    // Seconds per eating frame; the first two frames are the window to catch a fly
+    pub eat_frame_time: f32,
+    // Sprite tint, used for alternate skins
+    pub tint: Color,
+    // Seconds of invulnerability after taking damage
+    pub hit_invulnerability_time: f32,
+    // Seconds between sprite blinks while invulnerable
+    pub invulnerability_blink_interval: f32,
+    // Initial knockback speed, as percent of screen width per second
+    pub knockback_speed: f32,
+    // How quickly knockback speed dies down (per second)
+    pub knockback_damping: f32,
+    // Speed the dino walks back to its running spot, as percent of screen width per second
+    pub knockback_recovery_speed: f32,
+    // Seconds the dino is staggered (no input) after a hit
+    pub stagger_time: f32,
+    pub dash_mana_cost: f32,
+    // Seconds a dash lasts, and between dashes
+    pub dash_time: f32,
+    pub dash_cooldown: f32,
+    // Forward speed while dashing, as percent of screen width per second
+    pub dash_speed: f32,
+    pub slam_mana_cost: f32,
+    pub slam_cooldown: f32,
+    // Downward speed of a slam, in screen heights per second
+    pub slam_speed: f32,
+    // How far either side of the dino a slam reaches, as percent of screen width
+    pub slam_radius: f32,
+    // Forward slide after landing on wet ground, as percent of screen width per second
+    pub slip_speed: f32,
+}
+
+impl Default for DinoTuning {
+    fn default() -> Self {
+        Self {
+            max_mana: 1.0,
+            double_jump_mana_cost: 0.05,
+            flight_mana_drain: 0.1,
+            eat_frame_time: 0.1,
+            tint: WHITE,
+            hit_invulnerability_time: 1.0,
+            invulnerability_blink_interval: 0.08,
+            knockback_speed: 0.25,
+            knockback_damping: 8.0,
+            knockback_recovery_speed: 0.05,
+            stagger_time: 0.15,
+            dash_mana_cost: 0.15,
+            dash_time: 0.25,
+            dash_cooldown: 1.5,
+            dash_speed: 0.6,
+            slam_mana_cost: 0.1,
+            slam_cooldown: 2.0,
+            slam_speed: 2.5,
+            slam_radius: 0.12,
+            slip_speed: 0.2,
+        }
+    }
+}
@@ -1,5 +1,8 @@
-use macroquad::prelude::*;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
 use super::obstacle::Obstacle;
+use super::particles::Burst;
+use super::registry::{ObstacleTraits, ObstacleType, SavedObstacle};
 use super::dino::Effect;
 
 const SPRITE_COLS: usize = 2;
@@ -7,6 +10,17 @@ const SPRITE_ROWS: usize = 2;
 const FRAME_COUNT: usize = 4;
 const ANIMATION_SPEED: f32 = 0.15;
 
+#[derive(Serialize, Deserialize)]
+pub struct FlyState {
+    pub x_percent: f32,
+    pub y_percent: f32,
+    pub is_hit: bool,
+    pub current_frame: usize,
+    pub animation_timer: f32,
+    pub hit_frame: usize,
+    pub hit_timer: f32,
+}
+
 pub struct Fly {
     x_percent: f32,
     y_percent: f32,
@@ -20,15 +34,29 @@ pub struct Fly {
     hit_timer: f32,
 }
 
-impl Fly {
-    pub async fn load_textures() -> (Texture2D, Texture2D) {
-        let texture = load_texture("assets/bird.png").await.unwrap();
-        texture.set_filter(FilterMode::Nearest);
+const TEXTURE: &str = "assets/bird.png";
+const HIT_TEXTURE: &str = "assets/bird_hit.png";
 
-        let hit_texture = load_texture("assets/bird_hit.png").await.unwrap();
-        hit_texture.set_filter(FilterMode::Nearest);
-
-        (texture, hit_texture)
+impl Fly {
+    pub const ID: &'static str = "fly";
+
+    pub fn obstacle_type() -> ObstacleType {
+        ObstacleType {
+            id: Self::ID,
+            assets: &[TEXTURE, HIT_TEXTURE],
+            spawn_weight: 3,
+            traits: ObstacleTraits {
+                stompable: true,
+                edible: true,
+                hit_burst: |dino| dino.is_eating().then_some(Burst::Feathers),
+                ..ObstacleTraits::default()
+            },
+            spawn: |x, assets| Box::new(Fly::new(x, assets.texture(TEXTURE), assets.texture(HIT_TEXTURE))),
+            restore: |saved, assets| {
+                let state = saved.decode()?;
+                Some(Box::new(Fly::restore(state, assets.texture(TEXTURE), assets.texture(HIT_TEXTURE))))
+            },
+        }
     }
 
     pub fn new(x: f32, texture: Texture2D, hit_texture: Texture2D) -> Self {
@@ -49,6 +77,21 @@ impl Fly {
         }
     }
 
+    pub fn restore(state: FlyState, texture: Texture2D, hit_texture: Texture2D) -> Self {
+        Self {
+            x_percent: state.x_percent,
+            y_percent: state.y_percent,
+            size_percent: 0.1,
+            texture,
+            hit_texture,
+            is_hit: state.is_hit,
+            current_frame: state.current_frame,
+            animation_timer: state.animation_timer,
+            hit_frame: state.hit_frame,
+            hit_timer: state.hit_timer,
+        }
+    }
+
     fn x(&self) -> f32 {
         screen_width() * self.x_percent
     }
@@ -164,6 +207,28 @@ impl Obstacle for Fly {
         }
     }
 
+    fn snapshot(&self) -> SavedObstacle {
+        SavedObstacle::new(Self::ID, &FlyState {
+            x_percent: self.x_percent,
+            y_percent: self.y_percent,
+            is_hit: self.is_hit,
+            current_frame: self.current_frame,
+            animation_timer: self.animation_timer,
+            hit_frame: self.hit_frame,
+            hit_timer: self.hit_timer,
+        })
+    }
+
+    fn attract(&mut self, target: Vec2, strength: f32, dt: f32) {
+        if self.is_hit {
+            return;
+        }
+        // Drift vertically towards the target's mouth, faster as it gets close
+        let center_y = self.y() + self.height() / 2.0;
+        let target_y_percent = self.y_percent + (target.y - center_y) / screen_height();
+        self.y_percent += (target_y_percent - self.y_percent) * (strength * dt).min(1.0);
+    }
+
     fn on_hit(&mut self) {
         self.is_hit = true;
         self.hit_timer = 0.0;
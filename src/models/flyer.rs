@@ -0,0 +1,265 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::obstacle::Obstacle;
+use super::movement::{Movement, MovementPattern};
+use super::registry::{ObstacleAssets, ObstacleTraits, ObstacleType, SavedObstacle};
+use super::dino::Effect;
+
+const SPRITE_COLS: usize = 2;
+const SPRITE_ROWS: usize = 2;
+const FRAME_COUNT: usize = 4;
+const ANIMATION_SPEED: f32 = 0.12;
+const TEXTURE: &str = "assets/bird.png";
+const HIT_TEXTURE: &str = "assets/bird_hit.png";
+
+/// The flavours of hostile bird, each with its own movement pattern and colour
+#[derive(Clone, Copy)]
+pub enum FlyerKind {
+    Swooper,
+    Diver,
+    Charger,
+}
+
+impl FlyerKind {
+    pub const fn id(self) -> &'static str {
+        match self {
+            FlyerKind::Swooper => "swooper",
+            FlyerKind::Diver => "diver",
+            FlyerKind::Charger => "charger",
+        }
+    }
+
+    fn tint(self) -> Color {
+        match self {
+            FlyerKind::Swooper => SKYBLUE,
+            FlyerKind::Diver => ORANGE,
+            FlyerKind::Charger => PINK,
+        }
+    }
+
+    fn pattern(self) -> MovementPattern {
+        match self {
+            FlyerKind::Swooper => MovementPattern::SineWave { amplitude: 0.12, frequency: 0.6 },
+            FlyerKind::Diver => MovementPattern::Dive { trigger_distance: 0.45, speed: 0.6 },
+            FlyerKind::Charger => MovementPattern::HoverCharge { hover_x: 0.75, hover_time: 1.2, charge_speed: 0.5 },
+        }
+    }
+
+    // Spawn height range, as percent of screen height
+    fn height_range(self) -> (f32, f32) {
+        match self {
+            FlyerKind::Swooper => (0.35, 0.55),
+            FlyerKind::Diver => (0.1, 0.25),
+            FlyerKind::Charger => (0.2, 0.6),
+        }
+    }
+
+    pub fn obstacle_type(self) -> ObstacleType {
+        let spawn: fn(f32, &ObstacleAssets) -> Box<dyn Obstacle> = match self {
+            FlyerKind::Swooper => |x, assets| Box::new(Flyer::new(x, FlyerKind::Swooper, assets)),
+            FlyerKind::Diver => |x, assets| Box::new(Flyer::new(x, FlyerKind::Diver, assets)),
+            FlyerKind::Charger => |x, assets| Box::new(Flyer::new(x, FlyerKind::Charger, assets)),
+        };
+        // The kind is already in the saved id, so it isn't stored in the state again
+        let restore: fn(&SavedObstacle, &ObstacleAssets) -> Option<Box<dyn Obstacle>> = match self {
+            FlyerKind::Swooper => |saved, assets| Some(Box::new(Flyer::restore(saved.decode()?, FlyerKind::Swooper, assets))),
+            FlyerKind::Diver => |saved, assets| Some(Box::new(Flyer::restore(saved.decode()?, FlyerKind::Diver, assets))),
+            FlyerKind::Charger => |saved, assets| Some(Box::new(Flyer::restore(saved.decode()?, FlyerKind::Charger, assets))),
+        };
+        ObstacleType {
+            id: self.id(),
+            assets: &[TEXTURE, HIT_TEXTURE],
+            spawn_weight: 1,
+            traits: ObstacleTraits { stompable: true, ..ObstacleTraits::default() },
+            spawn,
+            restore,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FlyerState {
+    pub x_percent: f32,
+    pub y_percent: f32,
+    pub movement: Movement,
+    pub is_hit: bool,
+    pub current_frame: usize,
+    pub animation_timer: f32,
+    pub hit_frame: usize,
+    pub hit_timer: f32,
+}
+
+/// Hostile bird that follows a movement pattern; unlike `Fly` it can't be eaten
+pub struct Flyer {
+    kind: FlyerKind,
+    x_percent: f32,
+    y_percent: f32,
+    size_percent: f32,
+    movement: Movement,
+    // Latest dino position, in screen percentages
+    target: (f32, f32),
+    texture: Texture2D,
+    hit_texture: Texture2D,
+    is_hit: bool,
+    current_frame: usize,
+    animation_timer: f32,
+    hit_frame: usize,
+    hit_timer: f32,
+}
+
+impl Flyer {
+    pub fn new(x: f32, kind: FlyerKind, assets: &ObstacleAssets) -> Self {
+        let (min_y, max_y) = kind.height_range();
+        let y_percent = rand::gen_range(min_y, max_y);
+        Self {
+            kind,
+            x_percent: x / screen_width(),
+            y_percent,
+            size_percent: 0.1,
+            movement: Movement::new(kind.pattern(), y_percent),
+            target: (0.0, 0.0),
+            texture: assets.texture(TEXTURE),
+            hit_texture: assets.texture(HIT_TEXTURE),
+            is_hit: false,
+            current_frame: 0,
+            animation_timer: 0.0,
+            hit_frame: 0,
+            hit_timer: 0.0,
+        }
+    }
+
+    pub fn restore(state: FlyerState, kind: FlyerKind, assets: &ObstacleAssets) -> Self {
+        Self {
+            kind,
+            x_percent: state.x_percent,
+            y_percent: state.y_percent,
+            size_percent: 0.1,
+            movement: state.movement,
+            target: (0.0, 0.0),
+            texture: assets.texture(TEXTURE),
+            hit_texture: assets.texture(HIT_TEXTURE),
+            is_hit: state.is_hit,
+            current_frame: state.current_frame,
+            animation_timer: state.animation_timer,
+            hit_frame: state.hit_frame,
+            hit_timer: state.hit_timer,
+        }
+    }
+
+    fn x(&self) -> f32 {
+        screen_width() * self.x_percent
+    }
+
+    fn y(&self) -> f32 {
+        screen_height() * self.y_percent
+    }
+
+    fn size(&self) -> f32 {
+        screen_height() * self.size_percent
+    }
+}
+
+impl Obstacle for Flyer {
+    fn update(&mut self, speed: f32, dt: f32) {
+        let scroll_percent = speed / screen_width() * dt;
+        if self.is_hit {
+            // Tumble out of the fight with the world
+            self.x_percent -= scroll_percent;
+        } else {
+            self.movement.step(&mut self.x_percent, &mut self.y_percent, scroll_percent, self.target, dt);
+        }
+
+        // Flap faster while attacking
+        let animation_speed = if self.movement.is_attacking() { ANIMATION_SPEED / 2.0 } else { ANIMATION_SPEED };
+        self.animation_timer += dt;
+        if self.animation_timer >= animation_speed {
+            self.animation_timer = 0.0;
+            self.current_frame = (self.current_frame + 1) % FRAME_COUNT;
+        }
+
+        // Progress hit animation
+        if self.is_hit {
+            self.hit_timer += dt;
+            if self.hit_timer >= ANIMATION_SPEED {
+                self.hit_timer = 0.0;
+                self.hit_frame += 1;
+            }
+        }
+    }
+
+    fn draw(&self) {
+        let (texture, frame) = if self.is_hit {
+            (&self.hit_texture, self.hit_frame)
+        } else {
+            (&self.texture, self.current_frame)
+        };
+        let frame_col = frame % SPRITE_COLS;
+        let frame_row = frame / SPRITE_COLS;
+
+        let frame_width = texture.width() / SPRITE_COLS as f32;
+        let frame_height = texture.height() / SPRITE_ROWS as f32;
+
+        let source_rect = Rect::new(
+            frame_col as f32 * frame_width,
+            frame_row as f32 * frame_height,
+            frame_width,
+            frame_height,
+        );
+
+        draw_texture_ex(
+            texture,
+            self.x(),
+            self.y(),
+            self.kind.tint(),
+            DrawTextureParams {
+                source: Some(source_rect),
+                dest_size: Some(Vec2::new(self.size(), self.size())),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn get_hitbox(&self) -> Rect {
+        // No hitbox when already hit (prevent double-hit)
+        if self.is_hit {
+            Rect::new(0.0, 0.0, 0.0, 0.0)
+        } else {
+            Rect::new(self.x(), self.y() + self.size() / 4.0, self.size(), self.size() / 2.0)
+        }
+    }
+
+    fn is_off_screen(&self) -> bool {
+        // Divers can leave through the top or bottom as well
+        self.x() + self.size() < 0.0 || self.y() > screen_height() || self.y() + self.size() < 0.0
+    }
+
+    fn get_collision_effect(&self, _dino: &crate::models::Dino) -> Effect {
+        Effect::Damage(0.15)
+    }
+
+    fn snapshot(&self) -> SavedObstacle {
+        SavedObstacle::new(self.kind.id(), &FlyerState {
+            x_percent: self.x_percent,
+            y_percent: self.y_percent,
+            movement: self.movement,
+            is_hit: self.is_hit,
+            current_frame: self.current_frame,
+            animation_timer: self.animation_timer,
+            hit_frame: self.hit_frame,
+            hit_timer: self.hit_timer,
+        })
+    }
+
+    fn track_target(&mut self, target: Vec2) {
+        self.target = (target.x / screen_width(), target.y / screen_height());
+    }
+
+    fn on_hit(&mut self) {
+        self.is_hit = true;
+        self.hit_timer = 0.0;
+    }
+
+    fn should_remove(&self) -> bool {
+        self.is_hit && self.hit_frame >= FRAME_COUNT
+    }
+}
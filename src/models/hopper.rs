@@ -0,0 +1,196 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::obstacle::Obstacle;
+use super::registry::{ObstacleTraits, ObstacleType, SavedObstacle};
+use super::dino::{ground_y, Effect};
+
+// Hop physics, as percentages of screen height
+const HOP_VELOCITY: f32 = -0.9;
+const HOP_GRAVITY: f32 = 3.0;
+const HOP_INTERVAL: f32 = 0.7;
+const LANDING_SQUASH_TIME: f32 = 0.15;
+const BODY_COLOR: Color = Color::new(0.35, 0.6, 0.25, 1.0);
+
+#[derive(Serialize, Deserialize)]
+pub struct HopperState {
+    pub x_percent: f32,
+    pub height_percent: f32,
+    pub velocity_y: f32,
+    pub hop_timer: f32,
+    pub squash_timer: f32,
+    pub is_hit: bool,
+    pub rise_percent: f32,
+}
+
+/// Frog-like critter that hops up periodically while scrolling in
+pub struct Hopper {
+    x_percent: f32,
+    // Height above the ground, as percent of screen height
+    height_percent: f32,
+    // Height of the ground under it above the base ground level, as percent of screen height
+    rise_percent: f32,
+    // Vertical velocity, as percent of screen height per second (negative is up)
+    velocity_y: f32,
+    hop_timer: f32,
+    squash_timer: f32,
+    size_percent: f32,
+    is_hit: bool,
+}
+
+impl Hopper {
+    pub const ID: &'static str = "hopper";
+
+    pub fn obstacle_type() -> ObstacleType {
+        ObstacleType {
+            id: Self::ID,
+            assets: &[],
+            spawn_weight: 2,
+            traits: ObstacleTraits { stompable: true, small: true, ..ObstacleTraits::default() },
+            spawn: |x, _| Box::new(Hopper::new(x)),
+            restore: |saved, _| {
+                let state = saved.decode()?;
+                Some(Box::new(Hopper::restore(state)))
+            },
+        }
+    }
+
+    pub fn new(x: f32) -> Self {
+        Self {
+            x_percent: x / screen_width(),
+            height_percent: 0.0,
+            rise_percent: 0.0,
+            velocity_y: 0.0,
+            // Stagger hops so groups don't move in lockstep
+            hop_timer: rand::gen_range(0.0, HOP_INTERVAL),
+            squash_timer: 0.0,
+            size_percent: 0.08,
+            is_hit: false,
+        }
+    }
+
+    pub fn restore(state: HopperState) -> Self {
+        Self {
+            x_percent: state.x_percent,
+            height_percent: state.height_percent,
+            rise_percent: state.rise_percent,
+            velocity_y: state.velocity_y,
+            hop_timer: state.hop_timer,
+            squash_timer: state.squash_timer,
+            size_percent: 0.08,
+            is_hit: state.is_hit,
+        }
+    }
+
+    fn x(&self) -> f32 {
+        screen_width() * self.x_percent
+    }
+
+    fn y(&self) -> f32 {
+        ground_y() - self.size() - screen_height() * (self.rise_percent + self.height_percent)
+    }
+
+    fn size(&self) -> f32 {
+        screen_height() * self.size_percent
+    }
+
+    fn is_airborne(&self) -> bool {
+        self.height_percent > 0.0
+    }
+}
+
+impl Obstacle for Hopper {
+    fn update(&mut self, speed: f32, dt: f32) {
+        let speed_percent = speed / screen_width();
+        self.x_percent -= speed_percent * dt;
+
+        if self.is_airborne() || self.velocity_y < 0.0 {
+            self.velocity_y += HOP_GRAVITY * dt;
+            self.height_percent -= self.velocity_y * dt;
+            if self.height_percent <= 0.0 {
+                self.height_percent = 0.0;
+                self.velocity_y = 0.0;
+                self.squash_timer = LANDING_SQUASH_TIME;
+            }
+        } else {
+            self.squash_timer = (self.squash_timer - dt).max(0.0);
+            self.hop_timer += dt;
+            if self.hop_timer >= HOP_INTERVAL && !self.is_hit {
+                self.hop_timer = 0.0;
+                self.velocity_y = HOP_VELOCITY;
+            }
+        }
+    }
+
+    fn set_ground(&mut self, surface: Option<f32>) {
+        if let Some(y) = surface {
+            self.rise_percent = (ground_y() - y) / screen_height();
+        }
+    }
+
+    fn draw(&self) {
+        // Stretch while rising, squash right after landing
+        let (scale_x, scale_y) = if self.is_airborne() {
+            (0.85, 1.15)
+        } else if self.squash_timer > 0.0 {
+            (1.2, 0.8)
+        } else {
+            (1.0, 1.0)
+        };
+        let width = self.size() * scale_x;
+        let height = self.size() * scale_y;
+        let center_x = self.x() + self.size() / 2.0;
+        let bottom = self.y() + self.size();
+        let center_y = bottom - height / 2.0;
+        let color = if self.is_hit { GRAY } else { BODY_COLOR };
+
+        draw_ellipse(center_x, center_y, width / 2.0, height / 2.0, 0.0, color);
+        draw_ellipse_lines(center_x, center_y, width / 2.0, height / 2.0, 0.0, 2.0, DARKGREEN);
+
+        // Eyes on top, looking towards the dino
+        let eye_radius = width * 0.12;
+        let eye_y = center_y - height * 0.35;
+        for offset in [-0.22, 0.12] {
+            let eye_x = center_x + width * offset;
+            draw_circle(eye_x, eye_y, eye_radius, WHITE);
+            draw_circle(eye_x - eye_radius * 0.3, eye_y, eye_radius * 0.5, BLACK);
+        }
+    }
+
+    fn get_hitbox(&self) -> Rect {
+        // No hitbox when already hit (prevent double-hit)
+        if self.is_hit {
+            Rect::new(0.0, 0.0, 0.0, 0.0)
+        } else {
+            let inset = self.size() * 0.15;
+            Rect::new(self.x() + inset, self.y() + inset, self.size() - inset * 2.0, self.size() - inset)
+        }
+    }
+
+    fn is_off_screen(&self) -> bool {
+        self.x() + self.size() < 0.0
+    }
+
+    fn footprint(&self) -> Option<(f32, f32)> {
+        Some((self.x(), self.x() + self.size()))
+    }
+
+    fn get_collision_effect(&self, _dino: &crate::models::Dino) -> Effect {
+        Effect::Damage(0.15)
+    }
+
+    fn snapshot(&self) -> SavedObstacle {
+        SavedObstacle::new(Self::ID, &HopperState {
+            x_percent: self.x_percent,
+            height_percent: self.height_percent,
+            velocity_y: self.velocity_y,
+            hop_timer: self.hop_timer,
+            squash_timer: self.squash_timer,
+            is_hit: self.is_hit,
+            rise_percent: self.rise_percent,
+        })
+    }
+
+    fn on_hit(&mut self) {
+        self.is_hit = true;
+    }
+}
@@ -4,11 +4,46 @@ pub mod rock;
 pub mod fly;
 pub mod cloud;
 pub mod cactus;
+pub mod registry;
+pub mod pickup;
+pub mod coin;
+pub mod boulder;
+pub mod hopper;
+pub mod burrower;
+pub mod movement;
+pub mod flyer;
+pub mod projectile;
+pub mod spitter;
+pub mod thrower;
+pub mod terrain;
+pub mod boss;
+pub mod parallax;
+pub mod day_cycle;
+pub mod weather;
+pub mod particles;
+pub mod biome;
 
 pub use dino::Dino;
-pub use dino::ground_y;
-pub use obstacle::Obstacle;
-pub use rock::Rock;
+pub use dino::{DinoState, DinoTuning};
+pub use obstacle::{contact, Contact, Tracked};
+pub use registry::{ObstacleRegistry, SavedObstacle};
+pub use rock::RockKind;
 pub use fly::Fly;
-pub use cloud::Cloud;
-pub use cactus::Cactus;
+pub use cloud::{Cloud, CloudState};
+pub use cactus::CactusKind;
+pub use pickup::Pickup;
+pub use coin::Coin;
+pub use boulder::Boulder;
+pub use hopper::Hopper;
+pub use burrower::Burrower;
+pub use flyer::FlyerKind;
+pub use projectile::Projectile;
+pub use spitter::Spitter;
+pub use thrower::Thrower;
+pub use terrain::Terrain;
+pub use boss::{Boss, BossState};
+pub use parallax::Parallax;
+pub use day_cycle::DayCycle;
+pub use weather::{Weather, WeatherKind};
+pub use particles::{Burst, Particles};
+pub use biome::{Biome, Biomes};
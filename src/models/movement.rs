@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+/// How a moving obstacle travels, on top of scrolling with the world.
+/// All distances are percentages of the screen (x of width, y of height).
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum MovementPattern {
+    // Scrolls with the world at a fixed height
+    Straight,
+    // Bobs up and down around its spawn height
+    SineWave { amplitude: f32, frequency: f32 },
+    // Cruises until close to the target, then locks on and dives at it
+    Dive { trigger_distance: f32, speed: f32 },
+    // Stops at `hover_x`, follows the target's height, then charges straight ahead
+    HoverCharge { hover_x: f32, hover_time: f32, charge_speed: f32 },
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+enum Phase {
+    Approach,
+    Diving { velocity_x: f32, velocity_y: f32 },
+    Hovering,
+    Charging,
+}
+
+/// Movement component: a pattern plus the state needed to follow it
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Movement {
+    pattern: MovementPattern,
+    base_y_percent: f32,
+    timer: f32,
+    phase: Phase,
+}
+
+impl Movement {
+    pub fn new(pattern: MovementPattern, y_percent: f32) -> Self {
+        Self {
+            pattern,
+            base_y_percent: y_percent,
+            timer: 0.0,
+            phase: Phase::Approach,
+        }
+    }
+
+    /// True once the pattern has committed to an attack (dive or charge)
+    pub fn is_attacking(&self) -> bool {
+        matches!(self.phase, Phase::Diving { .. } | Phase::Charging)
+    }
+
+    /// Advance one step. `scroll_percent` is the world scroll this frame (already
+    /// multiplied by dt) and `target` the point being chased, in screen percentages.
+    pub fn step(&mut self, x_percent: &mut f32, y_percent: &mut f32, scroll_percent: f32, target: (f32, f32), dt: f32) {
+        self.timer += dt;
+        let (target_x, target_y) = target;
+
+        match self.pattern {
+            MovementPattern::Straight => {
+                *x_percent -= scroll_percent;
+            }
+            MovementPattern::SineWave { amplitude, frequency } => {
+                *x_percent -= scroll_percent;
+                *y_percent = self.base_y_percent + amplitude * (self.timer * frequency * std::f32::consts::TAU).sin();
+            }
+            MovementPattern::Dive { trigger_distance, speed } => match self.phase {
+                Phase::Diving { velocity_x, velocity_y } => {
+                    *x_percent -= scroll_percent - velocity_x * dt;
+                    *y_percent += velocity_y * dt;
+                }
+                _ => {
+                    *x_percent -= scroll_percent;
+                    if *x_percent - target_x < trigger_distance {
+                        // Lock on to where the target is now; no homing after that
+                        let dx = target_x - *x_percent;
+                        let dy = target_y - *y_percent;
+                        let length = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+                        self.phase = Phase::Diving {
+                            velocity_x: dx / length * speed,
+                            velocity_y: dy / length * speed,
+                        };
+                    }
+                }
+            },
+            MovementPattern::HoverCharge { hover_x, hover_time, charge_speed } => match self.phase {
+                Phase::Approach => {
+                    *x_percent -= scroll_percent;
+                    if *x_percent <= hover_x {
+                        self.phase = Phase::Hovering;
+                        self.timer = 0.0;
+                    }
+                }
+                Phase::Hovering => {
+                    // Hold position and drift towards the target's height
+                    *y_percent += (target_y - *y_percent) * (3.0 * dt).min(1.0);
+                    if self.timer >= hover_time {
+                        self.phase = Phase::Charging;
+                    }
+                }
+                _ => {
+                    *x_percent -= scroll_percent + charge_speed * dt;
+                }
+            },
+        }
+    }
+}
@@ -1,6 +1,42 @@
+use std::ops::{Deref, DerefMut};
+
 use super::Dino;
-use macroquad::prelude::Rect;
+use macroquad::prelude::{Circle, Rect, Vec2};
 use super::dino::Effect;
+use super::projectile::Projectile;
+use super::registry::{ObstacleTraits, SavedObstacle};
+
+/// Which side of an obstacle the dino touched
+#[derive(Clone, Copy, PartialEq)]
+pub enum Contact {
+    // Came down onto it from above
+    Top,
+    Side,
+    // Bumped into it from below
+    Bottom,
+}
+
+/// Contact between the dino's hitbox and an obstacle's, judged by where the
+/// dino's center is relative to the obstacle; None if they don't touch
+pub fn contact(hitbox: &Circle, rect: &Rect) -> Option<Contact> {
+    if !hitbox.overlaps_rect(rect) {
+        return None;
+    }
+    if hitbox.y < rect.top() {
+        Some(Contact::Top)
+    } else if hitbox.y > rect.bottom() {
+        Some(Contact::Bottom)
+    } else {
+        Some(Contact::Side)
+    }
+}
+
+/// Gap between the dino's hitbox and an obstacle's, in pixels; zero or less when they touch
+pub fn distance(hitbox: &Circle, rect: &Rect) -> f32 {
+    let closest_x = hitbox.x.clamp(rect.left(), rect.right());
+    let closest_y = hitbox.y.clamp(rect.top(), rect.bottom());
+    Vec2::new(hitbox.x - closest_x, hitbox.y - closest_y).length() - hitbox.r
+}
 
 /// Trait for all obstacles in the game (rocks, birds, etc.)
 pub trait Obstacle {
@@ -9,7 +45,78 @@ pub trait Obstacle {
     fn get_hitbox(&self) -> Rect;
     fn is_off_screen(&self) -> bool;
     fn get_collision_effect(&self, dino: &Dino) -> Effect;
+    fn snapshot(&self) -> SavedObstacle;
 
     fn on_hit(&mut self) {}
+    // Stretch of ground it stands on, as screen x (left, right), separate from the hitbox;
+    // None for flying obstacles. Spawning waits until this stretch is flat.
+    fn footprint(&self) -> Option<(f32, f32)> { None }
+    // Told the ground surface y under it each frame (None over a pit), for obstacles that move along the terrain
+    fn set_ground(&mut self, _ground_y: Option<f32>) {}
+    // Told where the dino is each frame, for obstacles that chase it
+    fn track_target(&mut self, _target: Vec2) {}
+    // Projectiles fired since the last call, handed over to the game
+    fn take_projectiles(&mut self) -> Vec<Projectile> { Vec::new() }
+    // Pull towards a point (the magnet power-up); only edible obstacles respond
+    fn attract(&mut self, _target: Vec2, _strength: f32, _dt: f32) {}
     fn should_remove(&self) -> bool { false }
 }
+
+/// An obstacle in play with its type's traits, plus how close the dino has come to it on the way past
+pub struct Tracked {
+    obstacle: Box<dyn Obstacle>,
+    traits: ObstacleTraits,
+    // Smallest gap to the dino while they overlapped horizontally, in pixels;
+    // zero or less means they touched
+    closest: f32,
+    // The dino's feet were above its top at some point while they overlapped horizontally
+    went_over: bool,
+}
+
+impl Tracked {
+    pub fn new(obstacle: Box<dyn Obstacle>, traits: ObstacleTraits) -> Self {
+        Self { obstacle, traits, closest: f32::INFINITY, went_over: false }
+    }
+
+    pub fn traits(&self) -> ObstacleTraits {
+        self.traits
+    }
+
+    /// Note how near the dino is and whether it's above, while the two overlap horizontally
+    pub fn track(&mut self, dino_hitbox: &Circle, feet_y: f32) {
+        let hitbox = self.obstacle.get_hitbox();
+        if hitbox.left() <= dino_hitbox.x + dino_hitbox.r && hitbox.right() >= dino_hitbox.x - dino_hitbox.r {
+            self.closest = self.closest.min(distance(dino_hitbox, &hitbox));
+            self.went_over |= feet_y < hitbox.top();
+        }
+    }
+
+    /// Whether the dino got over the top of it rather than under or through it
+    pub fn went_over(&self) -> bool {
+        self.went_over
+    }
+
+    /// Whether the dino ran into it at any point (including obstacles that stay solid after a hit)
+    pub fn touched(&self) -> bool {
+        self.closest <= 0.0
+    }
+
+    /// Smallest gap the dino got past it with, if it never touched
+    pub fn closest_miss(&self) -> Option<f32> {
+        (self.closest.is_finite() && !self.touched()).then_some(self.closest)
+    }
+}
+
+impl Deref for Tracked {
+    type Target = dyn Obstacle;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.obstacle
+    }
+}
+
+impl DerefMut for Tracked {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut *self.obstacle
+    }
+}
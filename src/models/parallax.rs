@@ -0,0 +1,242 @@
+use crate::prelude::*;
+
+// Size of generated silhouette tiles, in pixels
+const TILE_WIDTH: u16 = 512;
+const TILE_HEIGHT: u16 = 128;
+
+/// One background (or foreground) strip, tiled horizontally. Its tile is generated at
+/// load time: a silhouette filled below a ridge line.
+#[derive(Clone, Copy)]
+pub struct LayerDef {
+    pub color: Color,
+    // Ridge line as (waves per tile, amplitude) sine terms. Whole waves per tile keep
+    // it seamless; amplitudes are fractions of the tile height.
+    pub ridge: &'static [(u32, f32)],
+    // Fraction of the world scroll speed; below 1.0 is behind the action, above is in front
+    pub scroll_factor: f32,
+    // Vertical placement, as percent of screen height
+    pub top_percent: f32,
+    pub height_percent: f32,
+}
+
+impl LayerDef {
+    fn is_foreground(&self) -> bool {
+        self.scroll_factor > 1.0
+    }
+}
+
+/// Desert: far mesas, dunes, scrub and sand blowing past in front
+pub const DESERT_LAYERS: &[LayerDef] = &[
+    LayerDef {
+        color: Color::new(0.8, 0.6, 0.5, 1.0),
+        ridge: &[(2, 0.2), (6, 0.1), (13, 0.03)],
+        scroll_factor: 0.1,
+        top_percent: 0.5,
+        height_percent: 0.35,
+    },
+    LayerDef {
+        color: Color::new(0.9, 0.78, 0.55, 1.0),
+        ridge: &[(3, 0.12), (5, 0.05)],
+        scroll_factor: 0.3,
+        top_percent: 0.65,
+        height_percent: 0.2,
+    },
+    LayerDef {
+        color: Color::new(0.6, 0.6, 0.35, 1.0),
+        ridge: &[(5, 0.15), (11, 0.1)],
+        scroll_factor: 0.6,
+        top_percent: 0.78,
+        height_percent: 0.07,
+    },
+    LayerDef {
+        color: Color::new(0.85, 0.75, 0.55, 1.0),
+        ridge: &[(12, 0.2), (5, 0.1)],
+        scroll_factor: 1.2,
+        top_percent: 0.84,
+        height_percent: 0.015,
+    },
+];
+
+/// Jungle: far mountains, rolling hills, bushes and grass in front
+pub const JUNGLE_LAYERS: &[LayerDef] = &[
+    LayerDef {
+        color: Color::new(0.55, 0.62, 0.75, 1.0),
+        ridge: &[(2, 0.25), (5, 0.12), (11, 0.05)],
+        scroll_factor: 0.1,
+        top_percent: 0.45,
+        height_percent: 0.4,
+    },
+    LayerDef {
+        color: Color::new(0.3, 0.55, 0.35, 1.0),
+        ridge: &[(3, 0.15), (7, 0.06)],
+        scroll_factor: 0.3,
+        top_percent: 0.6,
+        height_percent: 0.25,
+    },
+    LayerDef {
+        color: Color::new(0.15, 0.45, 0.2, 1.0),
+        ridge: &[(4, 0.2), (9, 0.1), (23, 0.05)],
+        scroll_factor: 0.6,
+        top_percent: 0.74,
+        height_percent: 0.11,
+    },
+    LayerDef {
+        color: Color::new(0.25, 0.55, 0.2, 1.0),
+        ridge: &[(16, 0.3), (7, 0.15)],
+        scroll_factor: 1.2,
+        top_percent: 0.835,
+        height_percent: 0.02,
+    },
+];
+
+/// Tundra: snowy peaks, icy drifts and snow kicked up in front
+pub const TUNDRA_LAYERS: &[LayerDef] = &[
+    LayerDef {
+        color: Color::new(0.75, 0.8, 0.9, 1.0),
+        ridge: &[(3, 0.3), (7, 0.1)],
+        scroll_factor: 0.1,
+        top_percent: 0.42,
+        height_percent: 0.43,
+    },
+    LayerDef {
+        color: Color::new(0.85, 0.9, 0.95, 1.0),
+        ridge: &[(2, 0.1), (5, 0.05)],
+        scroll_factor: 0.3,
+        top_percent: 0.66,
+        height_percent: 0.19,
+    },
+    LayerDef {
+        color: Color::new(0.6, 0.7, 0.75, 1.0),
+        ridge: &[(6, 0.15), (13, 0.05)],
+        scroll_factor: 0.6,
+        top_percent: 0.78,
+        height_percent: 0.07,
+    },
+    LayerDef {
+        color: Color::new(0.95, 0.97, 1.0, 1.0),
+        ridge: &[(14, 0.2), (6, 0.1)],
+        scroll_factor: 1.2,
+        top_percent: 0.84,
+        height_percent: 0.015,
+    },
+];
+
+/// Volcano: the smoking cone itself, dark crags, rubble and ash in front
+pub const VOLCANO_LAYERS: &[LayerDef] = &[
+    LayerDef {
+        color: Color::new(0.35, 0.25, 0.25, 1.0),
+        ridge: &[(1, 0.35), (4, 0.08)],
+        scroll_factor: 0.1,
+        top_percent: 0.4,
+        height_percent: 0.45,
+    },
+    LayerDef {
+        color: Color::new(0.25, 0.2, 0.2, 1.0),
+        ridge: &[(4, 0.2), (9, 0.08)],
+        scroll_factor: 0.3,
+        top_percent: 0.62,
+        height_percent: 0.23,
+    },
+    LayerDef {
+        color: Color::new(0.5, 0.2, 0.1, 1.0),
+        ridge: &[(7, 0.15), (17, 0.08)],
+        scroll_factor: 0.6,
+        top_percent: 0.78,
+        height_percent: 0.07,
+    },
+    LayerDef {
+        color: Color::new(0.2, 0.17, 0.17, 1.0),
+        ridge: &[(15, 0.25), (6, 0.1)],
+        scroll_factor: 1.2,
+        top_percent: 0.84,
+        height_percent: 0.015,
+    },
+];
+
+struct Layer {
+    def: LayerDef,
+    texture: Texture2D,
+    // Distance scrolled, as percent of screen width
+    offset: f32,
+}
+
+/// Layered background where each layer scrolls at its own fraction of the world speed
+pub struct Parallax {
+    layers: Vec<Layer>,
+}
+
+impl Parallax {
+    pub fn new(defs: &[LayerDef]) -> Self {
+        let mut layers = Vec::with_capacity(defs.len());
+        for def in defs {
+            let texture = Texture2D::from_image(&silhouette(def.color, def.ridge));
+            texture.set_filter(FilterMode::Nearest);
+            layers.push(Layer { def: *def, texture, offset: 0.0 });
+        }
+        Self { layers }
+    }
+
+    pub fn update(&mut self, speed: f32, dt: f32) {
+        for layer in &mut self.layers {
+            layer.offset += speed * layer.def.scroll_factor / screen_width() * dt;
+        }
+    }
+
+    /// Layers behind the world, farthest first; the alpha fades the whole set in
+    pub fn draw_background(&self, alpha: f32) {
+        for layer in self.layers.iter().filter(|l| !l.def.is_foreground()) {
+            layer.draw(alpha);
+        }
+    }
+
+    /// Layers in front of the dino and obstacles
+    pub fn draw_foreground(&self, alpha: f32) {
+        for layer in self.layers.iter().filter(|l| l.def.is_foreground()) {
+            layer.draw(alpha);
+        }
+    }
+}
+
+impl Layer {
+    fn draw(&self, alpha: f32) {
+        let height = screen_height() * self.def.height_percent;
+        let width = height * self.texture.width() / self.texture.height();
+        let y = screen_height() * self.def.top_percent;
+        let start = -((self.offset * screen_width()) % width);
+
+        let mut x = start;
+        while x < screen_width() {
+            draw_texture_ex(
+                &self.texture,
+                x,
+                y,
+                Color::new(1.0, 1.0, 1.0, alpha),
+                DrawTextureParams {
+                    dest_size: Some(Vec2::new(width, height)),
+                    ..Default::default()
+                },
+            );
+            x += width;
+        }
+    }
+}
+
+// Seamless tile filled below a ridge line, transparent above it
+fn silhouette(color: Color, ridge: &[(u32, f32)]) -> Image {
+    let mut image = Image::gen_image_color(TILE_WIDTH, TILE_HEIGHT, Color::new(0.0, 0.0, 0.0, 0.0));
+    let height = TILE_HEIGHT as f32;
+    for x in 0..TILE_WIDTH as u32 {
+        let t = x as f32 / TILE_WIDTH as f32;
+        let wave: f32 = ridge
+            .iter()
+            .map(|(waves, amplitude)| amplitude * (t * *waves as f32 * std::f32::consts::TAU).sin())
+            .sum();
+        let total_amplitude: f32 = ridge.iter().map(|(_, amplitude)| amplitude).sum();
+        // Highest possible peak touches the top of the tile
+        let ridge_y = ((total_amplitude - wave) * height).clamp(0.0, height);
+        for y in ridge_y as u32..TILE_HEIGHT as u32 {
+            image.set_pixel(x, y, color);
+        }
+    }
+    image
+}
@@ -0,0 +1,211 @@
+use crate::prelude::*;
+
+// Particles alive at once; when the pool is full the oldest are recycled
+const MAX_PARTICLES: usize = 400;
+const DUST_COLORS: [Color; 2] = [Color::new(0.75, 0.68, 0.55, 0.8), Color::new(0.65, 0.58, 0.45, 0.8)];
+const MANA_COLORS: [Color; 3] = [BLUE, SKYBLUE, Color::new(0.6, 0.4, 1.0, 1.0)];
+
+/// Kinds of particle effect, each with its own look and motion
+#[derive(Clone, Copy, PartialEq)]
+pub enum Burst {
+    // Kicked up when the dino lands
+    Dust,
+    // Off a cactus when the dino runs into it
+    Sparks,
+    // Left over from an eaten fly
+    Feathers,
+    // Streamed behind the dino while it flies
+    ManaTrail,
+    // Rock fragments when a rock kills the dino
+    Debris,
+}
+
+// How a burst looks and moves; ranges are (min, max), speeds in screen heights per second,
+// sizes in percent of screen height
+struct BurstStyle {
+    count: usize,
+    colors: &'static [Color],
+    speed: (f32, f32),
+    // Launch direction range, in radians (0 = right, -PI/2 = up)
+    angle: (f32, f32),
+    gravity: f32,
+    // Fraction of velocity lost per second
+    drag: f32,
+    life: (f32, f32),
+    size: (f32, f32),
+}
+
+impl Burst {
+    fn style(self) -> BurstStyle {
+        use std::f32::consts::PI;
+        match self {
+            Burst::Dust => BurstStyle {
+                count: 10,
+                colors: &DUST_COLORS,
+                speed: (0.05, 0.2),
+                angle: (-PI, 0.0),
+                gravity: 0.1,
+                drag: 3.0,
+                life: (0.3, 0.6),
+                size: (0.006, 0.014),
+            },
+            Burst::Sparks => BurstStyle {
+                count: 12,
+                colors: &[YELLOW, ORANGE, WHITE],
+                speed: (0.4, 0.9),
+                angle: (-PI, 0.0),
+                gravity: 2.0,
+                drag: 1.0,
+                life: (0.15, 0.35),
+                size: (0.003, 0.006),
+            },
+            Burst::Feathers => BurstStyle {
+                count: 8,
+                colors: &[DARKGRAY, GRAY, LIGHTGRAY],
+                speed: (0.1, 0.3),
+                angle: (-PI, 0.0),
+                gravity: 0.15,
+                drag: 2.5,
+                life: (0.6, 1.2),
+                size: (0.005, 0.009),
+            },
+            Burst::ManaTrail => BurstStyle {
+                count: 1,
+                colors: &MANA_COLORS,
+                speed: (0.02, 0.08),
+                angle: (PI * 0.75, PI * 1.25),
+                gravity: 0.0,
+                drag: 1.0,
+                life: (0.3, 0.5),
+                size: (0.004, 0.008),
+            },
+            Burst::Debris => BurstStyle {
+                count: 16,
+                colors: &[DARKGRAY, GRAY, BROWN],
+                speed: (0.3, 0.8),
+                angle: (-PI * 0.9, -PI * 0.1),
+                gravity: 3.0,
+                drag: 0.5,
+                life: (0.6, 1.0),
+                size: (0.006, 0.014),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Particle {
+    // Position as percent of screen width and height
+    x_percent: f32,
+    y_percent: f32,
+    // Velocity in screen heights per second
+    velocity: Vec2,
+    life: f32,
+    max_life: f32,
+    size_percent: f32,
+    color: Color,
+    gravity: f32,
+    drag: f32,
+}
+
+/// Fixed pool of short-lived particles. Purely visual, so it isn't saved or rewound.
+pub struct Particles {
+    pool: Vec<Particle>,
+    // Next slot to hand out; wraps round and recycles the oldest particle
+    next: usize,
+    // Seconds until the next particle of a continuous stream
+    stream_timer: f32,
+}
+
+impl Particles {
+    pub fn new() -> Self {
+        let dead = Particle {
+            x_percent: 0.0,
+            y_percent: 0.0,
+            velocity: Vec2::ZERO,
+            life: 0.0,
+            max_life: 1.0,
+            size_percent: 0.0,
+            color: WHITE,
+            gravity: 0.0,
+            drag: 0.0,
+        };
+        Self {
+            pool: vec![dead; MAX_PARTICLES],
+            next: 0,
+            stream_timer: 0.0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for particle in &mut self.pool {
+            particle.life = 0.0;
+        }
+        self.stream_timer = 0.0;
+    }
+
+    /// Throw out a burst of particles from a point, in pixels
+    pub fn emit(&mut self, burst: Burst, position: Vec2) {
+        let style = burst.style();
+        for _ in 0..style.count {
+            self.spawn(&style, position);
+        }
+    }
+
+    /// Continuous stream of particles from a point, this many per second
+    pub fn stream(&mut self, burst: Burst, position: Vec2, per_second: f32, dt: f32) {
+        let style = burst.style();
+        self.stream_timer -= dt;
+        while self.stream_timer <= 0.0 {
+            self.spawn(&style, position);
+            self.stream_timer += 1.0 / per_second;
+        }
+    }
+
+    fn spawn(&mut self, style: &BurstStyle, position: Vec2) {
+        let angle = rand::gen_range(style.angle.0, style.angle.1);
+        let speed = rand::gen_range(style.speed.0, style.speed.1);
+        let life = rand::gen_range(style.life.0, style.life.1);
+        self.pool[self.next] = Particle {
+            x_percent: position.x / screen_width(),
+            y_percent: position.y / screen_height(),
+            velocity: Vec2::from_angle(angle) * speed,
+            life,
+            max_life: life,
+            size_percent: rand::gen_range(style.size.0, style.size.1),
+            color: style.colors[rand::gen_range(0, style.colors.len())],
+            gravity: style.gravity,
+            drag: style.drag,
+        };
+        self.next = (self.next + 1) % self.pool.len();
+    }
+
+    pub fn update(&mut self, speed: f32, dt: f32) {
+        let aspect = screen_height() / screen_width();
+        for particle in self.pool.iter_mut().filter(|p| p.life > 0.0) {
+            particle.life -= dt;
+            particle.velocity.y += particle.gravity * dt;
+            particle.velocity *= (1.0 - particle.drag * dt).max(0.0);
+            particle.x_percent += particle.velocity.x * aspect * dt;
+            particle.y_percent += particle.velocity.y * dt;
+            // Left behind as the world scrolls past
+            particle.x_percent -= speed / screen_width() * dt;
+        }
+    }
+
+    pub fn draw(&self) {
+        for particle in self.pool.iter().filter(|p| p.life > 0.0) {
+            // Shrink and fade out over the particle's life
+            let remaining = particle.life / particle.max_life;
+            let color = Color { a: particle.color.a * remaining, ..particle.color };
+            let size = screen_height() * particle.size_percent * (0.5 + 0.5 * remaining);
+            draw_rectangle(
+                particle.x_percent * screen_width() - size / 2.0,
+                particle.y_percent * screen_height() - size / 2.0,
+                size,
+                size,
+                color,
+            );
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::dino::{Effect, Status};
+
+const BOB_SPEED: f32 = 4.0;
+const BOB_HEIGHT_PERCENT: f32 = 0.015;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PickupKind {
+    Shield,
+    Magnet,
+    ManaPotion,
+    ScoreMultiplier,
+    SlowTime,
+}
+
+impl PickupKind {
+    fn random() -> Self {
+        match rand::gen_range(0, 5) {
+            0 => PickupKind::Shield,
+            1 => PickupKind::Magnet,
+            2 => PickupKind::ManaPotion,
+            3 => PickupKind::ScoreMultiplier,
+            _ => PickupKind::SlowTime,
+        }
+    }
+
+    fn icon(self) -> (&'static str, Color) {
+        match self {
+            PickupKind::Shield => ("S", SKYBLUE),
+            PickupKind::Magnet => ("M", RED),
+            PickupKind::ManaPotion => ("+", BLUE),
+            PickupKind::ScoreMultiplier => ("x2", GOLD),
+            PickupKind::SlowTime => ("T", VIOLET),
+        }
+    }
+}
+
+/// Collectible power-up that scrolls with the world
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Pickup {
+    kind: PickupKind,
+    x_percent: f32,
+    y_percent: f32,
+    size_percent: f32,
+    bob_timer: f32,
+    collected: bool,
+}
+
+impl Pickup {
+    pub fn new(x: f32) -> Self {
+        // Somewhere between running height and a single jump
+        let height_options = [0.78, 0.68, 0.58];
+        Self {
+            kind: PickupKind::random(),
+            x_percent: x / screen_width(),
+            y_percent: height_options[rand::gen_range(0, 3)],
+            size_percent: 0.05,
+            bob_timer: rand::gen_range(0.0, 1.0),
+            collected: false,
+        }
+    }
+
+    fn x(&self) -> f32 {
+        screen_width() * self.x_percent
+    }
+
+    fn y(&self) -> f32 {
+        let bob = (self.bob_timer * BOB_SPEED).sin() * BOB_HEIGHT_PERCENT;
+        screen_height() * (self.y_percent + bob)
+    }
+
+    fn size(&self) -> f32 {
+        screen_height() * self.size_percent
+    }
+
+    pub fn update(&mut self, speed: f32, dt: f32) {
+        let speed_percent = speed / screen_width();
+        self.x_percent -= speed_percent * dt;
+        self.bob_timer += dt;
+    }
+
+    pub fn draw(&self) {
+        let (label, color) = self.kind.icon();
+        let radius = self.size() / 2.0;
+        let center_x = self.x() + radius;
+        let center_y = self.y() + radius;
+
+        draw_circle(center_x, center_y, radius, color);
+        draw_circle_lines(center_x, center_y, radius, 2.0, WHITE);
+
+        let font_size = self.size() * 0.8;
+        let text_size = measure_text(label, None, font_size as u16, 1.0);
+        draw_text(
+            label,
+            center_x - text_size.width / 2.0,
+            center_y + text_size.height / 2.0,
+            font_size,
+            WHITE,
+        );
+    }
+
+    pub fn get_hitbox(&self) -> Rect {
+        Rect::new(self.x(), self.y(), self.size(), self.size())
+    }
+
+    pub fn is_off_screen(&self) -> bool {
+        self.x() + self.size() < 0.0
+    }
+
+    pub fn is_collected(&self) -> bool {
+        self.collected
+    }
+
+    /// Mark as collected and return what it does to the dino
+    pub fn collect(&mut self) -> Effect {
+        self.collected = true;
+        match self.kind {
+            PickupKind::Shield => Effect::Status(Status::Shield, 20.0),
+            PickupKind::Magnet => Effect::Status(Status::Magnet, 8.0),
+            PickupKind::ManaPotion => Effect::RestoreMana(0.5),
+            PickupKind::ScoreMultiplier => Effect::Status(Status::ScoreMultiplier, 10.0),
+            PickupKind::SlowTime => Effect::Status(Status::SlowTime, 5.0),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::dino::{ground_y, Effect, Status};
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ProjectileKind {
+    // Lobbed by spitting cacti
+    Seed,
+    // Dropped by throwers in the sky
+    Stone,
+    // Dropped by the pterodactyl boss; eating one hurts the boss
+    BossRock,
+}
+
+impl ProjectileKind {
+    // Downward acceleration, as percent of screen height per second squared
+    fn gravity(self) -> f32 {
+        match self {
+            ProjectileKind::Seed => 1.6,
+            ProjectileKind::Stone => 2.2,
+            ProjectileKind::BossRock => 2.0,
+        }
+    }
+
+    fn radius_percent(self) -> f32 {
+        match self {
+            ProjectileKind::Seed => 0.012,
+            ProjectileKind::Stone => 0.02,
+            ProjectileKind::BossRock => 0.03,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            ProjectileKind::Seed => DARKGREEN,
+            ProjectileKind::Stone => DARKGRAY,
+            ProjectileKind::BossRock => PURPLE,
+        }
+    }
+}
+
+/// Something thrown at the dino; lives in world space and scrolls with it
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Projectile {
+    kind: ProjectileKind,
+    x_percent: f32,
+    y_percent: f32,
+    // Own velocity on top of world scroll (x: percent of width, y: percent of height, per second)
+    velocity_x: f32,
+    velocity_y: f32,
+    // Seconds left before it fizzles out on its own
+    lifetime: f32,
+    is_spent: bool,
+}
+
+impl Projectile {
+    pub fn new(kind: ProjectileKind, x_percent: f32, y_percent: f32, velocity_x: f32, velocity_y: f32) -> Self {
+        Self {
+            kind,
+            x_percent,
+            y_percent,
+            velocity_x,
+            velocity_y,
+            lifetime: 4.0,
+            is_spent: false,
+        }
+    }
+
+    /// Launch velocity that lands on `target` after `flight_time` seconds, accounting for scroll
+    pub fn aimed_velocity(kind: ProjectileKind, from: (f32, f32), target: (f32, f32), scroll_percent: f32, flight_time: f32) -> (f32, f32) {
+        let velocity_x = (target.0 - from.0) / flight_time + scroll_percent;
+        let velocity_y = (target.1 - from.1) / flight_time - 0.5 * kind.gravity() * flight_time;
+        (velocity_x, velocity_y)
+    }
+
+    fn radius(&self) -> f32 {
+        screen_height() * self.kind.radius_percent()
+    }
+
+    fn center(&self) -> Vec2 {
+        Vec2::new(screen_width() * self.x_percent, screen_height() * self.y_percent)
+    }
+
+    pub fn update(&mut self, speed: f32, dt: f32) {
+        let scroll_percent = speed / screen_width();
+        self.velocity_y += self.kind.gravity() * dt;
+        self.x_percent += (self.velocity_x - scroll_percent) * dt;
+        self.y_percent += self.velocity_y * dt;
+        self.lifetime -= dt;
+
+        // Splat on the ground
+        if self.center().y + self.radius() >= ground_y() {
+            self.is_spent = true;
+        }
+    }
+
+    pub fn draw(&self) {
+        let center = self.center();
+        draw_circle(center.x, center.y, self.radius(), self.kind.color());
+        draw_circle_lines(center.x, center.y, self.radius(), 1.5, BLACK);
+    }
+
+    pub fn get_hitbox(&self) -> Rect {
+        let center = self.center();
+        let radius = self.radius();
+        Rect::new(center.x - radius, center.y - radius, radius * 2.0, radius * 2.0)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        let center = self.center();
+        self.is_spent || self.lifetime <= 0.0 || center.x + self.radius() < 0.0
+    }
+
+    /// Can be caught with the eat attack instead of hurting the dino
+    pub fn is_edible(&self) -> bool {
+        self.kind == ProjectileKind::BossRock
+    }
+
+    /// Caught in the dino's mouth
+    pub fn eat(&mut self) -> Effect {
+        self.is_spent = true;
+        Effect::Eaten
+    }
+
+    /// Mark as used up and return what it does to the dino
+    pub fn hit(&mut self) -> Effect {
+        self.is_spent = true;
+        match self.kind {
+            ProjectileKind::Seed => Effect::Combined(vec![
+                Effect::Damage(0.05),
+                Effect::Status(Status::Poison, 1.5),
+            ]),
+            ProjectileKind::Stone => Effect::Combined(vec![
+                Effect::Damage(0.15),
+                Effect::Status(Status::Stun, 0.2),
+            ]),
+            ProjectileKind::BossRock => Effect::Damage(0.15),
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use crate::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::obstacle::{Obstacle, Tracked};
+use super::particles::Burst;
+use super::{Biome, Boulder, Dino, Burrower, CactusKind, Fly, FlyerKind, Hopper, RockKind, Spitter, Thrower};
+
+/// Textures loaded for all registered obstacle types, keyed by asset path
+#[derive(Default)]
+pub struct ObstacleAssets {
+    textures: HashMap<&'static str, Texture2D>,
+}
+
+impl ObstacleAssets {
+    /// Texture for an asset path; the path must be listed in the type's `assets`
+    pub fn texture(&self, path: &str) -> Texture2D {
+        self.textures
+            .get(path)
+            .unwrap_or_else(|| panic!("obstacle asset not registered: {path}"))
+            .clone()
+    }
+}
+
+/// An obstacle serialized as its registry id plus type-specific state
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedObstacle {
+    pub id: String,
+    pub state: serde_json::Value,
+}
+
+impl SavedObstacle {
+    /// Panics if the state can't be turned into JSON, which only happens for state types
+    /// with non-string map keys; obstacle states are plain structs of numbers and flags
+    pub fn new<S: Serialize>(id: &str, state: &S) -> Self {
+        Self {
+            id: id.to_string(),
+            state: serde_json::to_value(state).expect("obstacle state should serialize to JSON"),
+        }
+    }
+
+    /// Decode the state back into the obstacle's own state type
+    pub fn decode<S: DeserializeOwned>(&self) -> Option<S> {
+        serde_json::from_value(self.state.clone()).ok()
+    }
+}
+
+/// Fixed facts about how one kind of obstacle reacts to the dino
+#[derive(Clone, Copy)]
+pub struct ObstacleTraits {
+    // Landing on top of it hits the obstacle instead of the dino
+    pub stompable: bool,
+    // A ground slam landing nearby destroys it
+    pub small: bool,
+    // The dino can eat it
+    pub edible: bool,
+    // Particles thrown off when the dino runs into it, checked after the hit's effect applies
+    pub hit_burst: fn(dino: &Dino) -> Option<Burst>,
+}
+
+impl Default for ObstacleTraits {
+    fn default() -> Self {
+        Self {
+            stompable: false,
+            small: false,
+            edible: false,
+            hit_burst: |_| None,
+        }
+    }
+}
+
+/// Everything the game needs to know about one kind of obstacle
+pub struct ObstacleType {
+    pub id: &'static str,
+    // Texture paths loaded once and shared by every instance
+    pub assets: &'static [&'static str],
+    // Relative chance of being picked by `spawn_random`
+    pub spawn_weight: u32,
+    pub traits: ObstacleTraits,
+    pub spawn: fn(x: f32, assets: &ObstacleAssets) -> Box<dyn Obstacle>,
+    pub restore: fn(saved: &SavedObstacle, assets: &ObstacleAssets) -> Option<Box<dyn Obstacle>>,
+}
+
+pub struct ObstacleRegistry {
+    types: Vec<ObstacleType>,
+    assets: ObstacleAssets,
+}
+
+impl ObstacleRegistry {
+    pub fn new() -> Self {
+        Self {
+            types: Vec::new(),
+            assets: ObstacleAssets::default(),
+        }
+    }
+
+    /// Registry with all built-in obstacle types, assets loaded
+    pub async fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register(RockKind::Stone.obstacle_type());
+        registry.register(RockKind::Ice.obstacle_type());
+        registry.register(CactusKind::Cactus.obstacle_type());
+        registry.register(CactusKind::LavaVent.obstacle_type());
+        registry.register(Fly::obstacle_type());
+        registry.register(Boulder::obstacle_type());
+        registry.register(Hopper::obstacle_type());
+        registry.register(Burrower::obstacle_type());
+        registry.register(FlyerKind::Swooper.obstacle_type());
+        registry.register(FlyerKind::Diver.obstacle_type());
+        registry.register(FlyerKind::Charger.obstacle_type());
+        registry.register(Spitter::obstacle_type());
+        registry.register(Thrower::obstacle_type());
+        registry.check_biomes();
+        registry.load_assets().await;
+        registry
+    }
+
+    // Every biome table has to name registered types and be able to roll at least one of them
+    fn check_biomes(&self) {
+        for biome in Biome::ALL {
+            for id in biome.obstacles() {
+                assert!(self.types.iter().any(|t| t.id == *id), "{} lists unregistered obstacle {id}", biome.name());
+            }
+            let total: u32 = self.types.iter().filter(|t| biome.obstacles().contains(&t.id)).map(|t| t.spawn_weight).sum();
+            assert!(total > 0, "{} has nothing to spawn", biome.name());
+        }
+    }
+
+    pub fn register(&mut self, obstacle_type: ObstacleType) {
+        self.types.push(obstacle_type);
+    }
+
+    /// Load every texture any registered type asked for
+    pub async fn load_assets(&mut self) {
+        let paths: Vec<&'static str> = self.types.iter().flat_map(|t| t.assets.iter().copied()).collect();
+        self.load_textures(&paths).await;
+    }
+
+    /// Load textures for things that aren't registered types but share their assets (e.g. the boss)
+    pub async fn load_textures(&mut self, paths: &[&'static str]) {
+        for &path in paths {
+            if self.assets.textures.contains_key(path) {
+                continue;
+            }
+            let texture = load_texture(path).await.unwrap();
+            texture.set_filter(FilterMode::Nearest);
+            self.assets.textures.insert(path, texture);
+        }
+    }
+
+    pub fn assets(&self) -> &ObstacleAssets {
+        &self.assets
+    }
+
+    /// Spawn a random obstacle out of the listed ids, weighted by each type's `spawn_weight`;
+    /// None if none of them can spawn
+    pub fn spawn_random(&self, x: f32, allowed: &[&str]) -> Option<Tracked> {
+        let candidates: Vec<&ObstacleType> = self.types.iter().filter(|t| allowed.contains(&t.id)).collect();
+        let total: u32 = candidates.iter().map(|t| t.spawn_weight).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rand::gen_range(0, total);
+        for obstacle_type in candidates {
+            if roll < obstacle_type.spawn_weight {
+                return Some(Tracked::new((obstacle_type.spawn)(x, &self.assets), obstacle_type.traits));
+            }
+            roll -= obstacle_type.spawn_weight;
+        }
+        None
+    }
+
+    /// Rebuild a saved obstacle; unknown ids or bad state are dropped
+    pub fn restore(&self, saved: &SavedObstacle) -> Option<Tracked> {
+        let obstacle_type = self.types.iter().find(|t| t.id == saved.id)?;
+        let obstacle = (obstacle_type.restore)(saved, &self.assets)?;
+        Some(Tracked::new(obstacle, obstacle_type.traits))
+    }
+}
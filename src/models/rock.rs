@@ -1,23 +1,86 @@
-use macroquad::prelude::*;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
 use super::obstacle::Obstacle;
+use super::particles::Burst;
+use super::registry::{ObstacleAssets, ObstacleTraits, ObstacleType, SavedObstacle};
 use super::dino::{ground_y, Effect};
 
+const TEXTURE: &str = "assets/rock.png";
+
+/// Looks a rock can take on in different biomes; they all behave the same
+#[derive(Clone, Copy)]
+pub enum RockKind {
+    Stone,
+    // Block of ice, in the tundra
+    Ice,
+}
+
+impl RockKind {
+    pub const fn id(self) -> &'static str {
+        match self {
+            RockKind::Stone => "rock",
+            RockKind::Ice => "ice_rock",
+        }
+    }
+
+    fn tint(self) -> Color {
+        match self {
+            RockKind::Stone => WHITE,
+            RockKind::Ice => Color::new(0.7, 0.9, 1.0, 0.9),
+        }
+    }
+
+    pub fn obstacle_type(self) -> ObstacleType {
+        let spawn: fn(f32, &ObstacleAssets) -> Box<dyn Obstacle> = match self {
+            RockKind::Stone => |x, assets| Box::new(Rock::new(x, RockKind::Stone, assets.texture(TEXTURE))),
+            RockKind::Ice => |x, assets| Box::new(Rock::new(x, RockKind::Ice, assets.texture(TEXTURE))),
+        };
+        // The kind is already in the saved id, so it isn't stored in the state again
+        let restore: fn(&SavedObstacle, &ObstacleAssets) -> Option<Box<dyn Obstacle>> = match self {
+            RockKind::Stone => |saved, assets| Some(Box::new(Rock::restore(saved.decode()?, RockKind::Stone, assets.texture(TEXTURE)))),
+            RockKind::Ice => |saved, assets| Some(Box::new(Rock::restore(saved.decode()?, RockKind::Ice, assets.texture(TEXTURE)))),
+        };
+        ObstacleType {
+            id: self.id(),
+            assets: &[TEXTURE],
+            spawn_weight: 4,
+            traits: ObstacleTraits {
+                small: true,
+                hit_burst: |dino| dino.is_dead().then_some(Burst::Debris),
+                ..ObstacleTraits::default()
+            },
+            spawn,
+            restore,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RockState {
+    pub x_percent: f32,
+}
+
 pub struct Rock {
+    kind: RockKind,
     x_percent: f32,
     size_percent: f32,
     texture: Texture2D,
 }
 
 impl Rock {
-    pub async fn load_texture() -> Texture2D {
-        let texture = load_texture("assets/rock.png").await.unwrap();
-        texture.set_filter(FilterMode::Nearest);
-        texture
+    pub fn new(x: f32, kind: RockKind, texture: Texture2D) -> Self {
+        Self {
+            kind,
+            x_percent: x / screen_width(),
+            size_percent: 0.125,
+            texture,
+        }
     }
 
-    pub fn new(x: f32, texture: Texture2D) -> Self {
+    pub fn restore(state: RockState, kind: RockKind, texture: Texture2D) -> Self {
         Self {
-            x_percent: x / screen_width(),
+            kind,
+            x_percent: state.x_percent,
             size_percent: 0.125,
             texture,
         }
@@ -51,7 +114,7 @@ impl Obstacle for Rock {
             &self.texture,
             self.x(),
             self.y(),
-            WHITE,
+            self.kind.tint(),
             DrawTextureParams {
                 dest_size: Some(Vec2::new(self.width(), self.height())),
                 ..Default::default()
@@ -68,7 +131,17 @@ impl Obstacle for Rock {
         self.x() + self.width() < 0.0
     }
 
+    fn footprint(&self) -> Option<(f32, f32)> {
+        Some((self.x(), self.x() + self.width()))
+    }
+
     fn get_collision_effect(&self, _dino: &crate::models::Dino) -> Effect {
         Effect::Kill
     }
+
+    fn snapshot(&self) -> SavedObstacle {
+        SavedObstacle::new(self.kind.id(), &RockState {
+            x_percent: self.x_percent,
+        })
+    }
 }
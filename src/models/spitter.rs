@@ -0,0 +1,185 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::obstacle::Obstacle;
+use super::projectile::{Projectile, ProjectileKind};
+use super::registry::{ObstacleTraits, ObstacleType, SavedObstacle};
+use super::dino::{ground_y, Effect};
+
+const TEXTURE: &str = "assets/cactus.png";
+const FIRE_INTERVAL: f32 = 1.6;
+const SEED_FLIGHT_TIME: f32 = 0.9;
+// Squash animation when a seed is spat out
+const RECOIL_TIME: f32 = 0.2;
+const TINT: Color = Color::new(0.75, 0.9, 0.5, 1.0);
+
+#[derive(Serialize, Deserialize)]
+pub struct SpitterState {
+    pub x_percent: f32,
+    pub fire_timer: f32,
+    pub recoil_timer: f32,
+    pub is_hit: bool,
+}
+
+/// Cactus that lobs poisonous seeds at the dino
+pub struct Spitter {
+    x_percent: f32,
+    size_percent: f32,
+    fire_timer: f32,
+    recoil_timer: f32,
+    is_hit: bool,
+    // Latest dino position and world scroll, in screen percentages
+    target: (f32, f32),
+    scroll_percent: f32,
+    pending: Vec<Projectile>,
+    texture: Texture2D,
+}
+
+impl Spitter {
+    pub const ID: &'static str = "spitter";
+
+    pub fn obstacle_type() -> ObstacleType {
+        ObstacleType {
+            id: Self::ID,
+            assets: &[TEXTURE],
+            spawn_weight: 1,
+            traits: ObstacleTraits { stompable: true, small: true, ..ObstacleTraits::default() },
+            spawn: |x, assets| Box::new(Spitter::new(x, assets.texture(TEXTURE))),
+            restore: |saved, assets| {
+                let state = saved.decode()?;
+                Some(Box::new(Spitter::restore(state, assets.texture(TEXTURE))))
+            },
+        }
+    }
+
+    pub fn new(x: f32, texture: Texture2D) -> Self {
+        Self::restore(
+            SpitterState {
+                x_percent: x / screen_width(),
+                fire_timer: FIRE_INTERVAL / 2.0,
+                recoil_timer: 0.0,
+                is_hit: false,
+            },
+            texture,
+        )
+    }
+
+    pub fn restore(state: SpitterState, texture: Texture2D) -> Self {
+        Self {
+            x_percent: state.x_percent,
+            size_percent: 0.13,
+            fire_timer: state.fire_timer,
+            recoil_timer: state.recoil_timer,
+            is_hit: state.is_hit,
+            target: (0.0, 0.0),
+            scroll_percent: 0.0,
+            pending: Vec::new(),
+            texture,
+        }
+    }
+
+    fn x(&self) -> f32 {
+        screen_width() * self.x_percent
+    }
+
+    fn y(&self) -> f32 {
+        ground_y() - self.height() + 10.0  // Adjust offset to sit on ground
+    }
+
+    fn width(&self) -> f32 {
+        let aspect_ratio = self.texture.width() / self.texture.height();
+        self.height() * aspect_ratio
+    }
+
+    fn height(&self) -> f32 {
+        screen_height() * self.size_percent
+    }
+
+    fn fire(&mut self) {
+        let mouth = (
+            (self.x() + self.width() / 2.0) / screen_width(),
+            self.y() / screen_height(),
+        );
+        let (velocity_x, velocity_y) =
+            Projectile::aimed_velocity(ProjectileKind::Seed, mouth, self.target, self.scroll_percent, SEED_FLIGHT_TIME);
+        self.pending.push(Projectile::new(ProjectileKind::Seed, mouth.0, mouth.1, velocity_x, velocity_y));
+        self.recoil_timer = RECOIL_TIME;
+    }
+}
+
+impl Obstacle for Spitter {
+    fn update(&mut self, speed: f32, dt: f32) {
+        self.scroll_percent = speed / screen_width();
+        self.x_percent -= self.scroll_percent * dt;
+        self.recoil_timer = (self.recoil_timer - dt).max(0.0);
+
+        // Only shoot while on screen and still in front of the dino
+        let on_screen = self.x() + self.width() < screen_width();
+        if self.is_hit || !on_screen || self.x_percent <= self.target.0 {
+            return;
+        }
+        self.fire_timer -= dt;
+        if self.fire_timer <= 0.0 {
+            self.fire_timer = FIRE_INTERVAL;
+            self.fire();
+        }
+    }
+
+    fn draw(&self) {
+        // Squash down briefly after spitting
+        let squash = 1.0 - 0.15 * (self.recoil_timer / RECOIL_TIME);
+        let height = self.height() * squash;
+        let color = if self.is_hit { GRAY } else { TINT };
+        draw_texture_ex(
+            &self.texture,
+            self.x(),
+            self.y() + self.height() - height,
+            color,
+            DrawTextureParams {
+                dest_size: Some(Vec2::new(self.width(), height)),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn get_hitbox(&self) -> Rect {
+        // No hitbox when already hit (prevent double-hit)
+        if self.is_hit {
+            Rect::new(0.0, 0.0, 0.0, 0.0)
+        } else {
+            Rect::new(self.x() + self.width() / 3.5, self.y(), self.width() / 2.5, self.height() * 0.9)
+        }
+    }
+
+    fn is_off_screen(&self) -> bool {
+        self.x() + self.width() < 0.0
+    }
+
+    fn footprint(&self) -> Option<(f32, f32)> {
+        Some((self.x(), self.x() + self.width()))
+    }
+
+    fn get_collision_effect(&self, _dino: &crate::models::Dino) -> Effect {
+        Effect::Damage(0.1)
+    }
+
+    fn snapshot(&self) -> SavedObstacle {
+        SavedObstacle::new(Self::ID, &SpitterState {
+            x_percent: self.x_percent,
+            fire_timer: self.fire_timer,
+            recoil_timer: self.recoil_timer,
+            is_hit: self.is_hit,
+        })
+    }
+
+    fn track_target(&mut self, target: Vec2) {
+        self.target = (target.x / screen_width(), target.y / screen_height());
+    }
+
+    fn take_projectiles(&mut self) -> Vec<Projectile> {
+        std::mem::take(&mut self.pending)
+    }
+
+    fn on_hit(&mut self) {
+        self.is_hit = true;
+    }
+}
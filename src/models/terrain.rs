@@ -0,0 +1,284 @@
+use std::collections::VecDeque;
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::rng::Rng;
+
+// Flat ground level, as percent of screen height (ground_y is this in pixels)
+pub const BASE_HEIGHT: f32 = 0.85;
+// How far past the right edge terrain is generated (percent of screen width)
+const LOOKAHEAD: f32 = 1.5;
+const PLATFORM_THICKNESS: f32 = 0.025;
+const EDGE_COLOR: Color = DARKBROWN;
+const PIT_COLOR: Color = Color::new(0.2, 0.13, 0.08, 1.0);
+// Distance between ground detail slots, in percent of screen width
+const DETAIL_SPACING: f32 = 0.025;
+const TUFT_COLOR: Color = Color::new(0.35, 0.55, 0.2, 1.0);
+const BONE_COLOR: Color = Color::new(0.95, 0.92, 0.85, 1.0);
+
+/// Decoration drawn at a fixed spot on the ground
+#[derive(Clone, Copy)]
+enum GroundDetail {
+    Pebble,
+    Tuft,
+    Bone,
+}
+
+impl GroundDetail {
+    /// What sits in a detail slot, and how deep below the surface (percent of screen height).
+    /// Derived from the slot number alone, so details scroll with the ground instead of flickering.
+    fn for_slot(slot: i64) -> Option<(Self, f32)> {
+        let hash = slot_hash(slot);
+        let depth = (hash >> 8 & 0xff) as f32 / 255.0 * 0.04;
+        match hash % 20 {
+            0..=3 => Some((GroundDetail::Pebble, 0.005 + depth)),
+            4 | 5 => Some((GroundDetail::Tuft, 0.0)),
+            6 => Some((GroundDetail::Bone, 0.02 + depth)),
+            _ => None,
+        }
+    }
+
+    fn draw(self, x: f32, y: f32) {
+        let unit = screen_height() * 0.006;
+        match self {
+            GroundDetail::Pebble => draw_rectangle(x, y, unit, unit, GRAY),
+            GroundDetail::Tuft => {
+                for (dx, lean) in [(-1.0, -0.6), (0.0, 0.0), (1.0, 0.6)] {
+                    draw_line(x + dx * unit * 0.6, y, x + (dx + lean) * unit, y - unit * 2.5, 1.5, TUFT_COLOR);
+                }
+            }
+            GroundDetail::Bone => {
+                draw_line(x - unit * 1.5, y, x + unit * 1.5, y, unit * 0.6, BONE_COLOR);
+                for end in [-1.5, 1.5] {
+                    draw_circle(x + end * unit, y - unit * 0.3, unit * 0.45, BONE_COLOR);
+                    draw_circle(x + end * unit, y + unit * 0.3, unit * 0.45, BONE_COLOR);
+                }
+            }
+        }
+    }
+}
+
+// Cheap integer hash (splitmix64 finaliser) for stable per-slot randomness
+fn slot_hash(slot: i64) -> u32 {
+    let mut x = (slot as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    (x ^ (x >> 31)) as u32
+}
+
+/// A stretch of ground. Positions are world x in percent of screen width,
+/// heights are the ground surface y in percent of screen height.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct Segment {
+    start: f32,
+    length: f32,
+    start_height: f32,
+    end_height: f32,
+    is_pit: bool,
+    // One-way ledge floating above this stretch
+    platform: Option<f32>,
+}
+
+impl Segment {
+    fn flat(start: f32, length: f32, height: f32) -> Self {
+        Self { start, length, start_height: height, end_height: height, is_pit: false, platform: None }
+    }
+
+    fn end(&self) -> f32 {
+        self.start + self.length
+    }
+
+    fn height_at(&self, world_x: f32) -> f32 {
+        let t = ((world_x - self.start) / self.length).clamp(0.0, 1.0);
+        self.start_height + (self.end_height - self.start_height) * t
+    }
+}
+
+/// Scrolling heightmap with pits, gentle hills and raised ledges
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Terrain {
+    segments: VecDeque<Segment>,
+    // Distance scrolled so far, in percent of screen width
+    offset: f32,
+    rng: Rng,
+}
+
+impl Terrain {
+    pub fn new(rng_seed: u64) -> Self {
+        let mut terrain = Self {
+            segments: VecDeque::new(),
+            offset: 0.0,
+            rng: Rng::new(rng_seed),
+        };
+        // Start with a long flat run so the first seconds are calm
+        terrain.segments.push_back(Segment::flat(-0.5, 2.5, BASE_HEIGHT));
+        terrain.generate();
+        terrain
+    }
+
+    /// Flat ground with a single pit, for tests
+    #[cfg(test)]
+    pub(crate) fn with_pit(pit_start: f32, pit_width: f32) -> Self {
+        let mut terrain = Self {
+            segments: VecDeque::new(),
+            offset: 0.0,
+            rng: Rng::new(0),
+        };
+        terrain.segments.push_back(Segment::flat(-0.5, pit_start + 0.5, BASE_HEIGHT));
+        terrain.segments.push_back(Segment { is_pit: true, ..Segment::flat(pit_start, pit_width, BASE_HEIGHT) });
+        terrain.segments.push_back(Segment::flat(pit_start + pit_width, 5.0, BASE_HEIGHT));
+        terrain
+    }
+
+    pub fn update(&mut self, speed: f32, dt: f32) {
+        self.offset += speed / screen_width() * dt;
+        while self.segments.front().is_some_and(|s| s.end() < self.offset - 0.1) {
+            self.segments.pop_front();
+        }
+        self.generate();
+    }
+
+    fn generate(&mut self) {
+        let Some(mut end) = self.segments.back().map(Segment::end) else {
+            return;
+        };
+        if end >= self.offset + 1.0 + LOOKAHEAD {
+            return;
+        }
+
+        let until = self.offset + 1.0 + LOOKAHEAD;
+        let segments = &mut self.segments;
+        self.rng.draw(|rng| {
+            while end < until {
+                // Every feature is followed by flat ground, so obstacles have room to spawn
+                match rng.gen_range(0, 4) {
+                    0 => {
+                        let width = rng.gen_range(0.06, 0.1);
+                        segments.push_back(Segment { is_pit: true, ..Segment::flat(end, width, BASE_HEIGHT) });
+                        end += width;
+                    }
+                    1 => {
+                        // Gentle hill: up, plateau, down
+                        let top = BASE_HEIGHT - rng.gen_range(0.03, 0.07);
+                        let slope = rng.gen_range(0.12, 0.2);
+                        let plateau = rng.gen_range(0.1, 0.3);
+                        segments.push_back(Segment { end_height: top, ..Segment::flat(end, slope, BASE_HEIGHT) });
+                        segments.push_back(Segment::flat(end + slope, plateau, top));
+                        segments.push_back(Segment { end_height: BASE_HEIGHT, ..Segment::flat(end + slope + plateau, slope, top) });
+                        end += slope * 2.0 + plateau;
+                    }
+                    2 => {
+                        let width = rng.gen_range(0.2, 0.35);
+                        let height = rng.gen_range(0.58, 0.68);
+                        segments.push_back(Segment { platform: Some(height), ..Segment::flat(end, width, BASE_HEIGHT) });
+                        end += width;
+                    }
+                    _ => {}
+                }
+                let flat = rng.gen_range(0.4, 0.9);
+                segments.push_back(Segment::flat(end, flat, BASE_HEIGHT));
+                end += flat;
+            }
+        });
+    }
+
+    /// Ground surface the dino would stand on between two screen x positions (pixels),
+    /// given where its feet are; None if there's only pit underneath.
+    /// Once the middle of the feet is over a pit the ground around it no longer holds the dino up,
+    /// so it drops in rather than straddling the gap.
+    /// Ledges only count when the feet are at or above them (one-way platforms).
+    pub fn surface_under(&self, left: f32, right: f32, feet_y: f32) -> Option<f32> {
+        let from = self.offset + left / screen_width();
+        let to = self.offset + right / screen_width();
+        let feet_percent = feet_y / screen_height();
+        let over_pit = self.ground_at((from + to) / 2.0).is_none();
+
+        let mut best: Option<f32> = None;
+        for segment in self.segments.iter().filter(|s| s.end() > from && s.start < to) {
+            let mut consider = |height: f32| {
+                best = Some(best.map_or(height, |b: f32| b.min(height)));
+            };
+            if !segment.is_pit && !over_pit {
+                let lo = from.max(segment.start);
+                let hi = to.min(segment.end());
+                consider(segment.height_at(lo).min(segment.height_at(hi)));
+            }
+            if let Some(platform) = segment.platform {
+                if feet_percent <= platform + 0.01 {
+                    consider(platform);
+                }
+            }
+        }
+        best
+    }
+
+    /// True if the ground between two screen x positions (pixels) is all plain flat base ground
+    pub fn is_flat_between(&self, left: f32, right: f32) -> bool {
+        let from = self.offset + left / screen_width();
+        let to = self.offset + right / screen_width();
+        let covered = self.segments.back().is_some_and(|s| s.end() >= to);
+        covered
+            && self
+                .segments
+                .iter()
+                .filter(|s| s.end() > from && s.start < to)
+                .all(|s| !s.is_pit && s.start_height == BASE_HEIGHT && s.end_height == BASE_HEIGHT)
+    }
+
+    /// Ground surface y (pixels) at a screen x, None over a pit. Ledges don't count.
+    pub fn ground_height(&self, x: f32) -> Option<f32> {
+        self.ground_at(self.offset + x / screen_width()).map(|height| height * screen_height())
+    }
+
+    /// Height of solid ground at a world x, None over a pit
+    fn ground_at(&self, world_x: f32) -> Option<f32> {
+        self.segments
+            .iter()
+            .find(|s| s.start <= world_x && world_x < s.end())
+            .filter(|s| !s.is_pit)
+            .map(|s| s.height_at(world_x))
+    }
+
+    /// Ground in the given colour (it changes with the biome)
+    pub fn draw(&self, ground_color: Color) {
+        let bottom = screen_height();
+        for segment in &self.segments {
+            let x0 = (segment.start - self.offset) * screen_width();
+            let x1 = (segment.end() - self.offset) * screen_width();
+            if x1 < 0.0 || x0 > screen_width() {
+                continue;
+            }
+            let y0 = segment.start_height * screen_height();
+            let y1 = segment.end_height * screen_height();
+
+            if segment.is_pit {
+                draw_rectangle(x0, y0, x1 - x0, bottom - y0, PIT_COLOR);
+            } else {
+                // Quad from the surface down to the bottom of the screen
+                draw_triangle(Vec2::new(x0, y0), Vec2::new(x1, y1), Vec2::new(x1, bottom), ground_color);
+                draw_triangle(Vec2::new(x0, y0), Vec2::new(x1, bottom), Vec2::new(x0, bottom), ground_color);
+                draw_line(x0, y0, x1, y1, 2.0, EDGE_COLOR);
+            }
+
+            if let Some(platform) = segment.platform {
+                let y = platform * screen_height();
+                let thickness = PLATFORM_THICKNESS * screen_height();
+                draw_rectangle(x0, y, x1 - x0, thickness, ground_color);
+                draw_rectangle_lines(x0, y, x1 - x0, thickness, 2.0, EDGE_COLOR);
+            }
+        }
+
+        // Pebbles, grass tufts and bones at fixed spots along the ground
+        let first_slot = (self.offset / DETAIL_SPACING).floor() as i64;
+        let last_slot = ((self.offset + 1.0) / DETAIL_SPACING).ceil() as i64;
+        for slot in first_slot..=last_slot {
+            let world_x = slot as f32 * DETAIL_SPACING;
+            let (Some((detail, depth)), Some(height)) = (GroundDetail::for_slot(slot), self.ground_at(world_x)) else {
+                continue;
+            };
+            let x = (world_x - self.offset) * screen_width();
+            detail.draw(x, (height + depth) * screen_height());
+        }
+    }
+}
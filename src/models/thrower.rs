@@ -0,0 +1,182 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::obstacle::Obstacle;
+use super::projectile::{Projectile, ProjectileKind};
+use super::registry::{ObstacleTraits, ObstacleType, SavedObstacle};
+use super::dino::Effect;
+
+const SPRITE_COLS: usize = 2;
+const SPRITE_ROWS: usize = 2;
+const FRAME_COUNT: usize = 4;
+const ANIMATION_SPEED: f32 = 0.15;
+const TEXTURE: &str = "assets/bird.png";
+const THROW_INTERVAL: f32 = 1.1;
+const STONE_FLIGHT_TIME: f32 = 0.8;
+// Only throws while this far ahead of the dino (percent of screen width)
+const THROW_RANGE: (f32, f32) = (0.1, 0.6);
+const TINT: Color = Color::new(0.6, 0.6, 0.65, 1.0);
+
+#[derive(Serialize, Deserialize)]
+pub struct ThrowerState {
+    pub x_percent: f32,
+    pub y_percent: f32,
+    pub throw_timer: f32,
+    pub current_frame: usize,
+    pub animation_timer: f32,
+}
+
+/// Bird high up in the sky that drops stones ahead of the dino
+pub struct Thrower {
+    x_percent: f32,
+    y_percent: f32,
+    size_percent: f32,
+    throw_timer: f32,
+    current_frame: usize,
+    animation_timer: f32,
+    // Latest dino position and world scroll, in screen percentages
+    target: (f32, f32),
+    scroll_percent: f32,
+    pending: Vec<Projectile>,
+    texture: Texture2D,
+}
+
+impl Thrower {
+    pub const ID: &'static str = "thrower";
+
+    pub fn obstacle_type() -> ObstacleType {
+        ObstacleType {
+            id: Self::ID,
+            assets: &[TEXTURE],
+            spawn_weight: 1,
+            traits: ObstacleTraits::default(),
+            spawn: |x, assets| Box::new(Thrower::new(x, assets.texture(TEXTURE))),
+            restore: |saved, assets| {
+                let state = saved.decode()?;
+                Some(Box::new(Thrower::restore(state, assets.texture(TEXTURE))))
+            },
+        }
+    }
+
+    pub fn new(x: f32, texture: Texture2D) -> Self {
+        Self::restore(
+            ThrowerState {
+                x_percent: x / screen_width(),
+                y_percent: rand::gen_range(0.08, 0.18),
+                throw_timer: 0.0,
+                current_frame: 0,
+                animation_timer: 0.0,
+            },
+            texture,
+        )
+    }
+
+    pub fn restore(state: ThrowerState, texture: Texture2D) -> Self {
+        Self {
+            x_percent: state.x_percent,
+            y_percent: state.y_percent,
+            size_percent: 0.09,
+            throw_timer: state.throw_timer,
+            current_frame: state.current_frame,
+            animation_timer: state.animation_timer,
+            target: (0.0, 0.0),
+            scroll_percent: 0.0,
+            pending: Vec::new(),
+            texture,
+        }
+    }
+
+    fn x(&self) -> f32 {
+        screen_width() * self.x_percent
+    }
+
+    fn y(&self) -> f32 {
+        screen_height() * self.y_percent
+    }
+
+    fn size(&self) -> f32 {
+        screen_height() * self.size_percent
+    }
+}
+
+impl Obstacle for Thrower {
+    fn update(&mut self, speed: f32, dt: f32) {
+        self.scroll_percent = speed / screen_width();
+        self.x_percent -= self.scroll_percent * dt;
+
+        self.animation_timer += dt;
+        if self.animation_timer >= ANIMATION_SPEED {
+            self.animation_timer = 0.0;
+            self.current_frame = (self.current_frame + 1) % FRAME_COUNT;
+        }
+
+        let distance = self.x_percent - self.target.0;
+        self.throw_timer -= dt;
+        if self.throw_timer <= 0.0 && distance > THROW_RANGE.0 && distance < THROW_RANGE.1 {
+            self.throw_timer = THROW_INTERVAL;
+            let hand = (
+                (self.x() + self.size() / 2.0) / screen_width(),
+                (self.y() + self.size()) / screen_height(),
+            );
+            let (velocity_x, velocity_y) =
+                Projectile::aimed_velocity(ProjectileKind::Stone, hand, self.target, self.scroll_percent, STONE_FLIGHT_TIME);
+            self.pending.push(Projectile::new(ProjectileKind::Stone, hand.0, hand.1, velocity_x, velocity_y));
+        }
+    }
+
+    fn draw(&self) {
+        let frame_col = self.current_frame % SPRITE_COLS;
+        let frame_row = self.current_frame / SPRITE_COLS;
+
+        let frame_width = self.texture.width() / SPRITE_COLS as f32;
+        let frame_height = self.texture.height() / SPRITE_ROWS as f32;
+
+        let source_rect = Rect::new(
+            frame_col as f32 * frame_width,
+            frame_row as f32 * frame_height,
+            frame_width,
+            frame_height,
+        );
+
+        draw_texture_ex(
+            &self.texture,
+            self.x(),
+            self.y(),
+            TINT,
+            DrawTextureParams {
+                source: Some(source_rect),
+                dest_size: Some(Vec2::new(self.size(), self.size())),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn get_hitbox(&self) -> Rect {
+        Rect::new(self.x(), self.y() + self.size() / 4.0, self.size(), self.size() / 2.0)
+    }
+
+    fn is_off_screen(&self) -> bool {
+        self.x() + self.size() < 0.0
+    }
+
+    fn get_collision_effect(&self, _dino: &crate::models::Dino) -> Effect {
+        Effect::Damage(0.1)
+    }
+
+    fn snapshot(&self) -> SavedObstacle {
+        SavedObstacle::new(Self::ID, &ThrowerState {
+            x_percent: self.x_percent,
+            y_percent: self.y_percent,
+            throw_timer: self.throw_timer,
+            current_frame: self.current_frame,
+            animation_timer: self.animation_timer,
+        })
+    }
+
+    fn track_target(&mut self, target: Vec2) {
+        self.target = (target.x / screen_width(), target.y / screen_height());
+    }
+
+    fn take_projectiles(&mut self) -> Vec<Projectile> {
+        std::mem::take(&mut self.pending)
+    }
+}
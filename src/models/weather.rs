@@ -0,0 +1,176 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::day_cycle::draw_fade_beyond;
+
+// Seconds weather takes to build up and to clear
+const FADE_TIME: f32 = 2.0;
+const MAX_DROPS: usize = 200;
+// Rain fall speed, as percent of screen height per second
+const DROP_SPEED: f32 = 1.6;
+const DROP_COLOR: Color = Color::new(0.6, 0.7, 0.9, 0.6);
+// Wind at full strength: sideways drift in screen widths per second, and the strongest
+// up/down gust in screen heights per second squared
+const WIND_PUSH: f32 = 0.12;
+const WIND_GUST: f32 = 1.2;
+// How far ahead of the dino you can see in thick fog, as percent of screen width
+const FOG_VISIBILITY: f32 = 0.35;
+const FOG_FADE: f32 = 0.25;
+const FOG_COLOR: Color = Color::new(0.85, 0.87, 0.9, 1.0);
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WeatherKind {
+    // Landings become slippery
+    Rain,
+    // Pushes the dino around while it's in the air
+    Wind,
+    // Hides obstacles until they're close
+    Fog,
+}
+
+impl WeatherKind {
+    fn label(self) -> &'static str {
+        match self {
+            WeatherKind::Rain => "RAIN",
+            WeatherKind::Wind => "WIND",
+            WeatherKind::Fog => "FOG",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            WeatherKind::Rain => BLUE,
+            WeatherKind::Wind => DARKGREEN,
+            WeatherKind::Fog => GRAY,
+        }
+    }
+}
+
+/// Current weather event, if any, with its visuals
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Weather {
+    kind: Option<WeatherKind>,
+    remaining: f32,
+    duration: f32,
+    // 0..1, how heavy this event is at its peak
+    intensity: f32,
+    // Which way the wind blows: -1 against the run, 1 with it
+    wind_direction: f32,
+    time: f32,
+    // Raindrop pool, in screen percentages; purely visual
+    #[serde(skip)]
+    drops: Vec<Vec2>,
+}
+
+impl Weather {
+    pub fn new() -> Self {
+        Self {
+            kind: None,
+            remaining: 0.0,
+            duration: 0.0,
+            intensity: 0.0,
+            wind_direction: 1.0,
+            time: 0.0,
+            drops: Vec::new(),
+        }
+    }
+
+    pub fn start(&mut self, kind: WeatherKind, duration: f32, intensity: f32, wind_direction: f32) {
+        self.kind = Some(kind);
+        self.remaining = duration;
+        self.duration = duration;
+        self.intensity = intensity;
+        self.wind_direction = wind_direction;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.kind.is_some()
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.time += dt;
+        if self.kind.is_some() {
+            self.remaining -= dt;
+            if self.remaining <= 0.0 {
+                self.kind = None;
+            }
+        }
+
+        if self.drops.is_empty() {
+            self.drops = (0..MAX_DROPS)
+                .map(|_| Vec2::new(rand::gen_range(0.0, 1.2), rand::gen_range(0.0, 1.0)))
+                .collect();
+        }
+        if self.kind == Some(WeatherKind::Rain) {
+            for drop in &mut self.drops {
+                drop.y += DROP_SPEED * dt;
+                drop.x -= DROP_SPEED * 0.2 * dt;
+                if drop.y > 1.0 {
+                    drop.y -= 1.0;
+                    drop.x = rand::gen_range(0.0, 1.2);
+                }
+            }
+        }
+    }
+
+    // Current strength of a kind of weather: ramps up at the start and down at the end
+    fn strength(&self, kind: WeatherKind) -> f32 {
+        if self.kind != Some(kind) {
+            return 0.0;
+        }
+        let elapsed = self.duration - self.remaining;
+        let fade = (elapsed / FADE_TIME).min(self.remaining / FADE_TIME).clamp(0.0, 1.0);
+        self.intensity * fade
+    }
+
+    pub fn is_raining(&self) -> bool {
+        self.strength(WeatherKind::Rain) > 0.0
+    }
+
+    /// Push on an airborne dino: steady drift along x, gusting up and down along y
+    pub fn wind(&self) -> Vec2 {
+        let strength = self.strength(WeatherKind::Wind);
+        let gust = (self.time * 1.3).sin() * (self.time * 0.7).cos();
+        Vec2::new(self.wind_direction * WIND_PUSH * strength, gust * WIND_GUST * strength)
+    }
+
+    pub fn draw(&self, dino_x: f32) {
+        let rain = self.strength(WeatherKind::Rain);
+        if rain > 0.0 {
+            let count = (MAX_DROPS as f32 * rain) as usize;
+            let length = screen_height() * 0.03;
+            for drop in self.drops.iter().take(count) {
+                let x = drop.x * screen_width();
+                let y = drop.y * screen_height();
+                draw_line(x, y, x + length * 0.2, y - length, 1.5, DROP_COLOR);
+            }
+        }
+
+        // Fog thickens with distance from the dino
+        let fog = self.strength(WeatherKind::Fog);
+        if fog > 0.0 {
+            let clear_until = dino_x + screen_width() * (FOG_VISIBILITY + (1.0 - fog));
+            draw_fade_beyond(clear_until, screen_width() * FOG_FADE, FOG_COLOR, 0.9 * fog);
+        }
+    }
+
+    /// Current weather in the top-right corner, with the time it has left
+    pub fn draw_indicator(&self) {
+        let Some(kind) = self.kind else {
+            return;
+        };
+        let font_size = screen_height() * 0.03;
+        let mut label = kind.label().to_string();
+        if kind == WeatherKind::Wind {
+            label.push_str(if self.wind_direction > 0.0 { " >>" } else { " <<" });
+        }
+        let width = measure_text(&label, None, font_size as u16, 1.0).width + font_size;
+        let x = screen_width() * 0.98 - width;
+        let y = font_size * 0.5;
+
+        draw_rectangle(x, y, width, font_size * 1.3, Color { a: 0.8, ..WHITE });
+        draw_rectangle_lines(x, y, width, font_size * 1.3, 2.0, kind.color());
+        draw_text(&label, x + font_size * 0.5, y + font_size, font_size, kind.color());
+        let fraction = (self.remaining / self.duration).clamp(0.0, 1.0);
+        draw_rectangle(x, y + font_size * 1.3, width * fraction, font_size * 0.15, kind.color());
+    }
+}
@@ -0,0 +1,4 @@
+// macroquad's prelude with the canvas size in place of the window's, so layout and gameplay
+// never pick up the window size by accident. Import this instead of macroquad::prelude.
+pub use macroquad::prelude::*;
+pub use crate::canvas::{screen_height, screen_width};
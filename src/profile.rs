@@ -0,0 +1,209 @@
+use std::fs;
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::models::DinoTuning;
+
+const PROFILE_PATH: &str = "profile.json";
+const LEADERBOARD_SIZE: usize = 10;
+/// Version of the scoring rules; bump it when scores stop being comparable with older runs.
+/// 1 was a point per frame, 2 is distance travelled plus event bonuses.
+pub const SCORING_VERSION: u32 = 2;
+
+/// Permanent upgrades bought in the shop
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Upgrade {
+    MaxMana,
+    CheapDoubleJump,
+    EfficientFlight,
+    LongerEatWindow,
+}
+
+impl Upgrade {
+    pub const ALL: [Upgrade; 4] = [
+        Upgrade::MaxMana,
+        Upgrade::CheapDoubleJump,
+        Upgrade::EfficientFlight,
+        Upgrade::LongerEatWindow,
+    ];
+    pub const MAX_LEVEL: u32 = 3;
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Upgrade::MaxMana => "Bigger mana pool",
+            Upgrade::CheapDoubleJump => "Cheaper double jump",
+            Upgrade::EfficientFlight => "Efficient flight",
+            Upgrade::LongerEatWindow => "Longer eat window",
+        }
+    }
+
+    /// Price of the next level, given the current one
+    pub fn cost(self, level: u32) -> u32 {
+        50 * 2u32.pow(level)
+    }
+}
+
+/// Alternate dino colours
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Skin {
+    Classic,
+    Ember,
+    Frost,
+    Shadow,
+}
+
+impl Skin {
+    pub const ALL: [Skin; 4] = [Skin::Classic, Skin::Ember, Skin::Frost, Skin::Shadow];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Skin::Classic => "Classic skin",
+            Skin::Ember => "Ember skin",
+            Skin::Frost => "Frost skin",
+            Skin::Shadow => "Shadow skin",
+        }
+    }
+
+    pub fn cost(self) -> u32 {
+        match self {
+            Skin::Classic => 0,
+            _ => 150,
+        }
+    }
+
+    pub fn tint(self) -> Color {
+        match self {
+            Skin::Classic => WHITE,
+            Skin::Ember => Color::new(1.0, 0.6, 0.4, 1.0),
+            Skin::Frost => Color::new(0.6, 0.85, 1.0, 1.0),
+            Skin::Shadow => Color::new(0.45, 0.45, 0.55, 1.0),
+        }
+    }
+}
+
+/// A finished run on the leaderboard
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub score: u32,
+    // Scoring rules the run was played under
+    pub scoring_version: u32,
+    // When the run started (seconds since the epoch); identifies the run
+    pub started_at: f64,
+}
+
+/// Progress kept between sessions: currency, upgrades, skins and best runs
+#[derive(Serialize, Deserialize)]
+pub struct Profile {
+    pub coins: u32,
+    upgrade_levels: Vec<(Upgrade, u32)>,
+    owned_skins: Vec<Skin>,
+    pub skin: Skin,
+    // Best runs first
+    pub leaderboard: Vec<LeaderboardEntry>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            coins: 0,
+            upgrade_levels: Vec::new(),
+            owned_skins: vec![Skin::Classic],
+            skin: Skin::Classic,
+            leaderboard: Vec::new(),
+        }
+    }
+}
+
+impl Profile {
+    /// Load the profile, starting fresh if there isn't a readable one
+    pub fn load() -> Self {
+        fs::read_to_string(PROFILE_PATH)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn write(&self) {
+        if let Ok(json) = serde_json::to_string(self) {
+            let _ = fs::write(PROFILE_PATH, json);
+        }
+    }
+
+    pub fn level(&self, upgrade: Upgrade) -> u32 {
+        self.upgrade_levels
+            .iter()
+            .find(|(u, _)| *u == upgrade)
+            .map(|(_, level)| *level)
+            .unwrap_or(0)
+    }
+
+    pub fn owns(&self, skin: Skin) -> bool {
+        self.owned_skins.contains(&skin)
+    }
+
+    /// Buy the next level of an upgrade; returns false if maxed or unaffordable
+    pub fn buy_upgrade(&mut self, upgrade: Upgrade) -> bool {
+        let level = self.level(upgrade);
+        let cost = upgrade.cost(level);
+        if level >= Upgrade::MAX_LEVEL || self.coins < cost {
+            return false;
+        }
+        self.coins -= cost;
+        match self.upgrade_levels.iter_mut().find(|(u, _)| *u == upgrade) {
+            Some((_, current)) => *current += 1,
+            None => self.upgrade_levels.push((upgrade, 1)),
+        }
+        true
+    }
+
+    /// Buy a skin if needed, then wear it; returns false if unaffordable
+    pub fn buy_or_equip_skin(&mut self, skin: Skin) -> bool {
+        if !self.owns(skin) {
+            if self.coins < skin.cost() {
+                return false;
+            }
+            self.coins -= skin.cost();
+            self.owned_skins.push(skin);
+        }
+        self.skin = skin;
+        true
+    }
+
+    /// Put a finished run on the leaderboard. A run that was rewound and finished
+    /// again keeps a single entry with its best score.
+    pub fn record_score(&mut self, started_at: f64, score: u32) {
+        match self.leaderboard.iter_mut().find(|e| e.started_at == started_at) {
+            Some(entry) => entry.score = entry.score.max(score),
+            None => self.leaderboard.push(LeaderboardEntry {
+                score,
+                scoring_version: SCORING_VERSION,
+                started_at,
+            }),
+        }
+        // Runs under the current rules rank above older ones, which aren't comparable
+        self.leaderboard.sort_by_key(|e| std::cmp::Reverse((e.scoring_version == SCORING_VERSION, e.score)));
+        self.leaderboard.truncate(LEADERBOARD_SIZE);
+    }
+
+    /// Best score under the current scoring rules
+    pub fn best_score(&self) -> u32 {
+        self.leaderboard
+            .iter()
+            .filter(|e| e.scoring_version == SCORING_VERSION)
+            .map(|e| e.score)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Dino tuning with every bought upgrade applied
+    pub fn dino_tuning(&self) -> DinoTuning {
+        let mut tuning = DinoTuning::default();
+        tuning.max_mana += 0.25 * self.level(Upgrade::MaxMana) as f32;
+        tuning.double_jump_mana_cost *= 1.0 - 0.2 * self.level(Upgrade::CheapDoubleJump) as f32;
+        tuning.flight_mana_drain *= 1.0 - 0.15 * self.level(Upgrade::EfficientFlight) as f32;
+        tuning.eat_frame_time += 0.02 * self.level(Upgrade::LongerEatWindow) as f32;
+        tuning.tint = self.skin.tint();
+        tuning
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::combo::Combo;
+use crate::director::Director;
+use crate::run_stats::RunStats;
+use crate::models::{Biomes, BossState, Coin, DayCycle, DinoState, Pickup, Projectile, SavedObstacle, Terrain, Weather};
+
+// How much gameplay history is kept, and how densely it is sampled
+const REWIND_SECONDS: f32 = 3.0;
+const SAMPLE_INTERVAL: f32 = 1.0 / 30.0;
+// Playback runs faster than real time so a full rewind doesn't drag
+const REWIND_PLAYBACK_SPEED: f32 = 2.0;
+
+/// Compact copy of everything needed to resume the world from a point in time
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub dino: DinoState,
+    pub obstacles: Vec<SavedObstacle>,
+    pub projectiles: Vec<Projectile>,
+    pub pickups: Vec<Pickup>,
+    pub coins: Vec<Coin>,
+    pub run_coins: u32,
+    pub terrain: Terrain,
+    pub day_cycle: DayCycle,
+    pub biomes: Biomes,
+    pub weather: Weather,
+    pub director: Director,
+    pub boss: Option<BossState>,
+    pub next_boss_score: u32,
+    pub score: u32,
+    pub score_progress: f32,
+    pub combo: Combo,
+    pub stats: RunStats,
+    pub game_speed_percent: f32,
+    pub spawn_timer: f32,
+    pub rng_seed: u64,
+}
+
+/// Ring buffer of recent snapshots
+pub struct RewindBuffer {
+    snapshots: VecDeque<Snapshot>,
+    sample_timer: f32,
+}
+
+impl RewindBuffer {
+    pub fn new() -> Self {
+        Self {
+            snapshots: VecDeque::with_capacity(Self::capacity()),
+            sample_timer: 0.0,
+        }
+    }
+
+    fn capacity() -> usize {
+        (REWIND_SECONDS / SAMPLE_INTERVAL).ceil() as usize
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.sample_timer = 0.0;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Advance the sample clock; returns true when a new snapshot should be pushed
+    pub fn sample_due(&mut self, dt: f32) -> bool {
+        self.sample_timer -= dt;
+        if self.sample_timer > 0.0 {
+            return false;
+        }
+        self.sample_timer += SAMPLE_INTERVAL;
+        true
+    }
+
+    pub fn push(&mut self, snapshot: Snapshot) {
+        if self.snapshots.len() >= Self::capacity() {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    /// Step backwards in time, returning the snapshot to restore (if any were consumed)
+    pub fn rewind(&mut self, dt: f32) -> Option<Snapshot> {
+        let steps = ((dt * REWIND_PLAYBACK_SPEED) / SAMPLE_INTERVAL).round().max(1.0) as usize;
+        let mut restored = None;
+        for _ in 0..steps {
+            // Keep the oldest sample so there is always somewhere to land
+            if self.snapshots.len() <= 1 {
+                break;
+            }
+            restored = self.snapshots.pop_back();
+        }
+        self.sample_timer = 0.0;
+        restored.or_else(|| self.snapshots.back().cloned())
+    }
+}
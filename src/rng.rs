@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::rand::RandGenerator;
+
+/// Random number stream owned by one subsystem. Only its seed is stored, so it is saved and
+/// rewound with the rest of the world and replays the same numbers, whatever else draws from
+/// the global RNG in between.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Rng {
+    seed: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Draw numbers from the stream; the next draw carries on where this one stopped
+    pub fn draw<T>(&mut self, f: impl FnOnce(&RandGenerator) -> T) -> T {
+        let generator = RandGenerator::new();
+        generator.srand(self.seed);
+        let result = f(&generator);
+        self.seed = generator.rand() as u64;
+        result
+    }
+}
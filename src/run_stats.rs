@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::combo::ScoreEvent;
+
+/// Tallies for the current run, shown on the game over screen
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct RunStats {
+    pub near_misses: u32,
+    pub stomps: u32,
+    pub flies_eaten: u32,
+    pub obstacles_cleared: u32,
+}
+
+impl RunStats {
+    pub fn record(&mut self, event: ScoreEvent) {
+        match event {
+            ScoreEvent::NearMiss => self.near_misses += 1,
+            ScoreEvent::Stomp => self.stomps += 1,
+            ScoreEvent::AteFly => self.flies_eaten += 1,
+            ScoreEvent::Cleared | ScoreEvent::FlewThrough => self.obstacles_cleared += 1,
+            ScoreEvent::BossHit => {}
+        }
+    }
+}
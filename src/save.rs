@@ -0,0 +1,36 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::CloudState;
+use crate::rewind::Snapshot;
+
+const SAVE_PATH: &str = "savegame.json";
+
+/// A run in progress, written on quit and offered as "Continue" on the title screen
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    pub world: Snapshot,
+    pub clouds: Vec<CloudState>,
+    pub high_score: u32,
+    pub rewind_charges: u32,
+}
+
+impl SaveGame {
+    /// Load the saved run, if there is one and it can be read
+    pub fn load() -> Option<Self> {
+        let json = fs::read_to_string(SAVE_PATH).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    pub fn write(&self) {
+        if let Ok(json) = serde_json::to_string(self) {
+            let _ = fs::write(SAVE_PATH, json);
+        }
+    }
+
+    /// Remove the save so a run can only be continued once
+    pub fn delete() {
+        let _ = fs::remove_file(SAVE_PATH);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::prelude::*;
+
+use crate::profile::{Profile, Skin, Upgrade};
+
+enum ShopItem {
+    Upgrade(Upgrade),
+    Skin(Skin),
+}
+
+/// Shop screen: spend coins on permanent upgrades and skins
+pub struct Shop {
+    selected: usize,
+    items: Vec<ShopItem>,
+}
+
+impl Shop {
+    pub fn new() -> Self {
+        let items = Upgrade::ALL
+            .iter()
+            .map(|u| ShopItem::Upgrade(*u))
+            .chain(Skin::ALL.iter().map(|s| ShopItem::Skin(*s)))
+            .collect();
+        Self { selected: 0, items }
+    }
+
+    /// Handle navigation and purchases; returns true if anything was bought or equipped
+    pub fn update(&mut self, profile: &mut Profile) -> bool {
+        if is_key_pressed(KeyCode::Down) {
+            self.selected = (self.selected + 1) % self.items.len();
+        }
+        if is_key_pressed(KeyCode::Up) {
+            self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+        }
+        if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space) {
+            return match self.items[self.selected] {
+                ShopItem::Upgrade(upgrade) => profile.buy_upgrade(upgrade),
+                ShopItem::Skin(skin) => profile.buy_or_equip_skin(skin),
+            };
+        }
+        false
+    }
+
+    pub fn draw(&self, profile: &Profile) {
+        let font_title = screen_height() * 0.06;
+        let font_item = screen_height() * 0.035;
+        let center_x = screen_width() / 2.0;
+        let left_x = screen_width() * 0.25;
+        let start_y = screen_height() * 0.2;
+
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(1.0, 1.0, 1.0, 0.8));
+
+        let title = format!("SHOP - {} coins", profile.coins);
+        let title_width = measure_text(&title, None, font_title as u16, 1.0).width;
+        draw_text(&title, center_x - title_width / 2.0, start_y, font_title, DARKGRAY);
+
+        for (i, item) in self.items.iter().enumerate() {
+            let (name, status, affordable) = match item {
+                ShopItem::Upgrade(upgrade) => {
+                    let level = profile.level(*upgrade);
+                    let status = if level >= Upgrade::MAX_LEVEL {
+                        format!("Lv {level}/{} - MAX", Upgrade::MAX_LEVEL)
+                    } else {
+                        format!("Lv {level}/{} - {} coins", Upgrade::MAX_LEVEL, upgrade.cost(level))
+                    };
+                    let affordable = level < Upgrade::MAX_LEVEL && profile.coins >= upgrade.cost(level);
+                    (upgrade.name(), status, affordable)
+                }
+                ShopItem::Skin(skin) => {
+                    let status = if profile.skin == *skin {
+                        "Equipped".to_string()
+                    } else if profile.owns(*skin) {
+                        "Owned - equip".to_string()
+                    } else {
+                        format!("{} coins", skin.cost())
+                    };
+                    (skin.name(), status, profile.owns(*skin) || profile.coins >= skin.cost())
+                }
+            };
+
+            let y = start_y + font_title + i as f32 * font_item * 1.5;
+            let color = if affordable { DARKGRAY } else { GRAY };
+            if i == self.selected {
+                draw_text(">", left_x - font_item, y, font_item, RED);
+            }
+            draw_text(name, left_x, y, font_item, color);
+            draw_text(&status, center_x + screen_width() * 0.05, y, font_item, color);
+        }
+
+        let hint = "UP/DOWN - Select   ENTER - Buy / Equip   S - Back";
+        let hint_width = measure_text(hint, None, font_item as u16, 1.0).width;
+        draw_text(hint, center_x - hint_width / 2.0, screen_height() * 0.8, font_item, GRAY);
+    }
+}